@@ -0,0 +1,42 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! Internal helpers to load structured configuration documents.
+//!
+//! Some settings (broker topologies, table maps) are too structured for plain
+//! environment variables, so they are read from JSON documents, or from YAML
+//! documents when the `yaml` feature is enabled.
+
+use serde::de::DeserializeOwned;
+use std::path::Path;
+
+use crate::errors::ConfigsError;
+
+/// Parses a JSON document, naming it as `what` in errors.
+pub(crate) fn from_json<T: DeserializeOwned>(what: &str, json: &str) -> Result<T, ConfigsError> {
+    serde_json::from_str(json).map_err(|e| ConfigsError::Parse(format!("{}: {}", what, e)))
+}
+
+/// Parses a YAML document, naming it as `what` in errors.
+#[cfg(feature = "yaml")]
+pub(crate) fn from_yaml<T: DeserializeOwned>(what: &str, yaml: &str) -> Result<T, ConfigsError> {
+    serde_yaml_ng::from_str(yaml).map_err(|e| ConfigsError::Parse(format!("{}: {}", what, e)))
+}
+
+/// Reads a document, parsing `.yaml` and `.yml` files as YAML and anything else as JSON.
+pub(crate) fn from_file<T: DeserializeOwned>(what: &str, path: &str) -> Result<T, ConfigsError> {
+    let content =
+        std::fs::read_to_string(path).map_err(|_| ConfigsError::FileNotFound(path.into()))?;
+
+    match Path::new(path).extension().and_then(|ext| ext.to_str()) {
+        #[cfg(feature = "yaml")]
+        Some("yaml" | "yml") => from_yaml(what, &content),
+        #[cfg(not(feature = "yaml"))]
+        Some("yaml" | "yml") => Err(ConfigsError::Parse(format!(
+            "{} ({}): YAML documents require the `yaml` feature",
+            what, path
+        ))),
+        _ => from_json(what, &content),
+    }
+}
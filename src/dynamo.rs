@@ -7,13 +7,35 @@
 //! This module provides configuration options for connecting to
 //! and working with Amazon DynamoDB.
 
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, HashSet},
+    fmt::{Display, Formatter, Result as FmtResult},
+    time::Duration,
+};
+
+use crate::{
+    aws::{
+        AWS_DEFAULT_REGION, AWS_DEFAULT_REGION_ENV_KEY, AWS_ENDPOINT_URL_ENV_KEY,
+        AWS_IGNORE_CONFIGURED_ENDPOINT_URLS_ENV_KEY, AWS_REGION_ENV_KEY, AwsService,
+    },
+    document,
+    environment::Environment,
+    errors::ConfigsError,
+    uri,
+};
+
 /// # DynamoConfigs
 ///
 /// Configuration structure for Amazon DynamoDB.
 ///
 /// This structure defines the connection parameters and settings for working
 /// with Amazon DynamoDB tables. It includes endpoint configuration, region selection,
-/// table name, and item expiration settings.
+/// the tables used by the service, and item expiration settings.
+///
+/// Without an endpoint the AWS SDK resolves the regional DynamoDB endpoint. In the
+/// `Environment::Local` environment the local emulator mode is enabled by default,
+/// pointing the client to DynamoDB Local on `http://localhost:8000`.
 ///
 /// ## Examples
 ///
@@ -25,13 +47,21 @@
 /// ```
 #[derive(Debug, Clone)]
 pub struct DynamoConfigs {
+    /// The environment the configuration was created for, set by `for_env`
+    /// (Default: Environment::default())
+    pub env: Environment,
     /// ENV KEY: "DYNAMO_ENDPOINT"
     ///
-    /// The DynamoDB endpoint URL (Default: "localhost")
-    pub endpoint: String,
+    /// The DynamoDB endpoint URL, `None` uses the AWS regional endpoint
+    /// (Default: "http://localhost:8000" in local mode, None otherwise)
+    pub endpoint: Option<String>,
+    /// ENV KEY: "DYNAMO_LOCAL"
+    ///
+    /// Whether to use a local DynamoDB emulator (Default: true in `Environment::Local`)
+    pub local: bool,
     /// ENV KEY: "DYNAMO_REGION"
     ///
-    /// The AWS region for DynamoDB (Default: "us-east-1")
+    /// The AWS region for DynamoDB (Default: "AWS_REGION", "AWS_DEFAULT_REGION" or "us-east-1")
     pub region: String,
     /// ENV KEY: "DYNAMO_TABLE"
     ///
@@ -41,44 +71,700 @@ pub struct DynamoConfigs {
     ///
     /// The default time-to-live (TTL) for items in seconds (Default: 31536000 - 1 year)
     pub expire: u64,
+    /// ENV KEY: "DYNAMO_TTL_ATTRIBUTE"
+    ///
+    /// The attribute holding the item expiration time, inherited by tables that do
+    /// not set their own (Default: None)
+    pub ttl_attribute: Option<String>,
+    /// ENV KEY: "DYNAMO_TABLES_FILE" or "DYNAMO_TABLES"
+    ///
+    /// Tables by logical name, loaded from a JSON (or, with the `yaml` feature, YAML)
+    /// file or from inline JSON. `{env}` in table names is replaced by the
+    /// current `Environment` (Default: empty)
+    pub tables: BTreeMap<String, DynamoTableConfigs>,
+    /// Error raised when the tables file cannot be read or the tables cannot be
+    /// parsed, reported by `validate()` (Default: None)
+    pub tables_error: Option<ConfigsError>,
+    /// ENV KEY: "DYNAMO_RETRY_MODE"
+    ///
+    /// The SDK retry mode (Default: DynamoRetryMode::Standard)
+    pub retry_mode: DynamoRetryMode,
+    /// ENV KEY: "DYNAMO_MAX_ATTEMPTS"
+    ///
+    /// Maximum attempts per operation, including the first one (Default: 3)
+    pub max_attempts: u32,
+    /// ENV KEY: "DYNAMO_CONNECT_TIMEOUT"
+    ///
+    /// Connection timeout in milliseconds, `None` uses the SDK default (Default: None)
+    pub connect_timeout: Option<Duration>,
+    /// ENV KEY: "DYNAMO_OPERATION_TIMEOUT"
+    ///
+    /// Timeout for an operation including its retries, in milliseconds (Default: None)
+    pub operation_timeout: Option<Duration>,
+    /// ENV KEY: "DYNAMO_OPERATION_ATTEMPT_TIMEOUT"
+    ///
+    /// Timeout for a single attempt of an operation, in milliseconds (Default: None)
+    pub operation_attempt_timeout: Option<Duration>,
+    /// ENV KEY: "DYNAMO_BILLING_MODE", "DYNAMO_READ_CAPACITY" and "DYNAMO_WRITE_CAPACITY"
+    ///
+    /// The billing mode inherited by tables that do not set their own
+    /// (Default: DynamoBillingMode::OnDemand)
+    pub billing_mode: DynamoBillingMode,
 }
 
 pub const DYNAMO_ENDPOINT_ENV_KEY: &str = "DYNAMO_ENDPOINT";
+pub const DYNAMO_LOCAL_ENV_KEY: &str = "DYNAMO_LOCAL";
 pub const DYNAMO_TABLE_ENV_KEY: &str = "DYNAMO_TABLE";
 pub const DYNAMO_REGION_ENV_KEY: &str = "DYNAMO_REGION";
 pub const DYNAMO_EXPIRE_ENV_KEY: &str = "DYNAMO_EXPIRE";
+pub const DYNAMO_TTL_ATTRIBUTE_ENV_KEY: &str = "DYNAMO_TTL_ATTRIBUTE";
+pub const DYNAMO_TABLES_FILE_ENV_KEY: &str = "DYNAMO_TABLES_FILE";
+pub const DYNAMO_TABLES_ENV_KEY: &str = "DYNAMO_TABLES";
+pub const DYNAMO_RETRY_MODE_ENV_KEY: &str = "DYNAMO_RETRY_MODE";
+pub const DYNAMO_MAX_ATTEMPTS_ENV_KEY: &str = "DYNAMO_MAX_ATTEMPTS";
+pub const DYNAMO_CONNECT_TIMEOUT_ENV_KEY: &str = "DYNAMO_CONNECT_TIMEOUT";
+pub const DYNAMO_OPERATION_TIMEOUT_ENV_KEY: &str = "DYNAMO_OPERATION_TIMEOUT";
+pub const DYNAMO_OPERATION_ATTEMPT_TIMEOUT_ENV_KEY: &str = "DYNAMO_OPERATION_ATTEMPT_TIMEOUT";
+pub const DYNAMO_BILLING_MODE_ENV_KEY: &str = "DYNAMO_BILLING_MODE";
+pub const DYNAMO_READ_CAPACITY_ENV_KEY: &str = "DYNAMO_READ_CAPACITY";
+pub const DYNAMO_WRITE_CAPACITY_ENV_KEY: &str = "DYNAMO_WRITE_CAPACITY";
+
+/// Endpoint of DynamoDB Local with its default port.
+pub const DYNAMO_LOCAL_ENDPOINT: &str = "http://localhost:8000";
+
+/// Placeholder replaced by the `Environment` in table names.
+const ENV_PLACEHOLDER: &str = "{env}";
 
 impl DynamoConfigs {
     /// Creates a new `DynamoConfigs` instance from environments variables.
     ///
     /// This method initializes the DynamoDB configuration from environments variables
-    /// for endpoint, region, table name, and item expiration settings.
+    /// for endpoint, region, tables, and item expiration settings. Table names are
+    /// rendered for `Environment::from_rust_env()`, and tables without TTL settings
+    /// inherit `ttl_attribute` and `expire`.
+    ///
+    /// An empty "DYNAMO_ENDPOINT" selects the AWS regional endpoint. When it is not
+    /// set, the AWS SDK "AWS_ENDPOINT_URL_DYNAMODB" and "AWS_ENDPOINT_URL" keys are
+    /// used unless "AWS_IGNORE_CONFIGURED_ENDPOINT_URLS" is true, then
+    /// `DYNAMO_LOCAL_ENDPOINT` in local mode.
     ///
     /// ## Returns
     ///
     /// A new `DynamoConfigs` from environments variables.
     pub fn new() -> Self {
-        let mut cfgs = Self::default();
+        let env = Environment::from_rust_env();
+        let mut cfgs = Self::for_env(&env);
 
-        cfgs.endpoint = std::env::var(DYNAMO_ENDPOINT_ENV_KEY).unwrap_or(cfgs.endpoint);
-        cfgs.region = std::env::var(DYNAMO_REGION_ENV_KEY).unwrap_or(cfgs.region);
+        cfgs.local = std::env::var(DYNAMO_LOCAL_ENV_KEY)
+            .ok()
+            .and_then(|v| v.parse::<bool>().ok())
+            .unwrap_or(cfgs.local);
+        let ignore_aws_endpoints = std::env::var(AWS_IGNORE_CONFIGURED_ENDPOINT_URLS_ENV_KEY)
+            .ok()
+            .and_then(|v| v.parse::<bool>().ok())
+            .unwrap_or_default();
+        cfgs.endpoint = match std::env::var(DYNAMO_ENDPOINT_ENV_KEY) {
+            Ok(endpoint) if endpoint.is_empty() => None,
+            Ok(endpoint) => Some(endpoint),
+            Err(_) if ignore_aws_endpoints => cfgs.local.then(|| DYNAMO_LOCAL_ENDPOINT.to_owned()),
+            Err(_) => [
+                AwsService::DynamoDb.endpoint_env_key(),
+                AWS_ENDPOINT_URL_ENV_KEY.into(),
+            ]
+            .iter()
+            .find_map(|key| std::env::var(key).ok().filter(|v| !v.is_empty()))
+            .or_else(|| cfgs.local.then(|| DYNAMO_LOCAL_ENDPOINT.to_owned())),
+        };
+        cfgs.region = [
+            DYNAMO_REGION_ENV_KEY,
+            AWS_REGION_ENV_KEY,
+            AWS_DEFAULT_REGION_ENV_KEY,
+        ]
+        .iter()
+        .find_map(|key| std::env::var(key).ok().filter(|v| !v.is_empty()))
+        .unwrap_or(cfgs.region);
         cfgs.table = std::env::var(DYNAMO_TABLE_ENV_KEY).unwrap_or(cfgs.table);
         cfgs.expire = std::env::var(DYNAMO_EXPIRE_ENV_KEY)
             .ok()
             .and_then(|v| v.parse::<u64>().ok())
             .unwrap_or(cfgs.expire);
+        cfgs.ttl_attribute = std::env::var(DYNAMO_TTL_ATTRIBUTE_ENV_KEY)
+            .ok()
+            .filter(|v| !v.is_empty())
+            .or(cfgs.ttl_attribute);
+        let tables = std::env::var(DYNAMO_TABLES_FILE_ENV_KEY)
+            .ok()
+            .map(|path| document::from_file("DynamoDB tables", &path))
+            .or_else(|| {
+                std::env::var(DYNAMO_TABLES_ENV_KEY)
+                    .ok()
+                    .map(|json| Self::tables_from_json(&json))
+            });
+        match tables {
+            Some(Ok(tables)) => cfgs.tables = tables,
+            Some(Err(e)) => cfgs.tables_error = Some(e),
+            None => {}
+        }
+        cfgs.retry_mode = std::env::var(DYNAMO_RETRY_MODE_ENV_KEY)
+            .map(|v| DynamoRetryMode::from(v.as_str()))
+            .unwrap_or(cfgs.retry_mode);
+        cfgs.max_attempts = std::env::var(DYNAMO_MAX_ATTEMPTS_ENV_KEY)
+            .ok()
+            .and_then(|v| v.parse::<u32>().ok())
+            .unwrap_or(cfgs.max_attempts);
+        cfgs.connect_timeout = env_millis(DYNAMO_CONNECT_TIMEOUT_ENV_KEY).or(cfgs.connect_timeout);
+        cfgs.operation_timeout =
+            env_millis(DYNAMO_OPERATION_TIMEOUT_ENV_KEY).or(cfgs.operation_timeout);
+        cfgs.operation_attempt_timeout =
+            env_millis(DYNAMO_OPERATION_ATTEMPT_TIMEOUT_ENV_KEY).or(cfgs.operation_attempt_timeout);
+        cfgs.billing_mode = match std::env::var(DYNAMO_BILLING_MODE_ENV_KEY)
+            .unwrap_or_default()
+            .to_lowercase()
+            .replace('-', "_")
+            .as_str()
+        {
+            "provisioned" => DynamoBillingMode::Provisioned {
+                read_capacity: std::env::var(DYNAMO_READ_CAPACITY_ENV_KEY)
+                    .ok()
+                    .and_then(|v| v.parse::<u64>().ok())
+                    .unwrap_or(DYNAMO_DEFAULT_CAPACITY),
+                write_capacity: std::env::var(DYNAMO_WRITE_CAPACITY_ENV_KEY)
+                    .ok()
+                    .and_then(|v| v.parse::<u64>().ok())
+                    .unwrap_or(DYNAMO_DEFAULT_CAPACITY),
+            },
+            "on_demand" | "pay_per_request" => DynamoBillingMode::OnDemand,
+            _ => cfgs.billing_mode,
+        };
 
-        cfgs
+        cfgs.with_env(&env)
     }
-}
 
-impl Default for DynamoConfigs {
-    fn default() -> Self {
+    /// Creates the default `DynamoConfigs` for an environment.
+    ///
+    /// The local emulator mode, and its endpoint, are only enabled for
+    /// `Environment::Local`.
+    ///
+    /// ## Parameters
+    ///
+    /// * `env` - The environment the service runs in
+    ///
+    /// ## Returns
+    ///
+    /// The default `DynamoConfigs` for `env`.
+    pub fn for_env(env: &Environment) -> Self {
+        let local = env.is_local();
+
         Self {
-            endpoint: "localhost".to_owned(),
-            region: "us-east-1".to_owned(),
+            env: *env,
+            endpoint: local.then(|| DYNAMO_LOCAL_ENDPOINT.to_owned()),
+            local,
+            region: AWS_DEFAULT_REGION.to_owned(),
             table: Default::default(),
             expire: 31536000,
+            ttl_attribute: None,
+            tables: Default::default(),
+            tables_error: None,
+            retry_mode: Default::default(),
+            max_attempts: 3,
+            connect_timeout: None,
+            operation_timeout: None,
+            operation_attempt_timeout: None,
+            billing_mode: Default::default(),
+        }
+    }
+
+    /// Parses a table map from a JSON document.
+    ///
+    /// The document is an object keyed by logical table name:
+    ///
+    /// ```json
+    /// {
+    ///   "sessions": {
+    ///     "name": "sessions-{env}",
+    ///     "ttl_attribute": "expires_at",
+    ///     "ttl": 3600,
+    ///     "partition_key": { "name": "id", "type": "S" }
+    ///   }
+    /// }
+    /// ```
+    ///
+    /// ## Parameters
+    ///
+    /// * `json` - The JSON document
+    ///
+    /// ## Returns
+    ///
+    /// The tables by logical name, or a `ConfigsError::Parse`.
+    pub fn tables_from_json(
+        json: &str,
+    ) -> Result<BTreeMap<String, DynamoTableConfigs>, ConfigsError> {
+        document::from_json("DynamoDB tables", json)
+    }
+
+    /// Renders table names for an environment and applies the table defaults.
+    ///
+    /// `env` becomes the configured environment. Every `{env}` in the table
+    /// names is replaced by the environment (e.g. `sessions-{env}` becomes
+    /// `sessions-prd`). Tables without a TTL attribute inherit `ttl_attribute`,
+    /// tables with a TTL attribute but no TTL inherit `expire`, and tables
+    /// without a billing mode inherit `billing_mode`.
+    ///
+    /// ## Parameters
+    ///
+    /// * `env` - The environment used to render table names and to validate
+    ///
+    /// ## Returns
+    ///
+    /// The `DynamoConfigs` with resolved tables.
+    pub fn with_env(mut self, env: &Environment) -> Self {
+        self.env = *env;
+        let env = env.to_string();
+
+        self.table = self.table.replace(ENV_PLACEHOLDER, &env);
+        for table in self.tables.values_mut() {
+            table.name = table.name.replace(ENV_PLACEHOLDER, &env);
+            if table.ttl_attribute.is_none() {
+                table.ttl_attribute = self.ttl_attribute.clone();
+            }
+            if table.ttl_attribute.is_some() && table.ttl.is_none() {
+                table.ttl = Some(self.expire);
+            }
+            if table.billing_mode.is_none() {
+                table.billing_mode = Some(self.billing_mode);
+            }
+        }
+
+        self
+    }
+
+    /// Finds a table by logical name.
+    ///
+    /// ## Parameters
+    ///
+    /// * `name` - The logical table name, e.g. "sessions"
+    ///
+    /// ## Returns
+    ///
+    /// The table configuration, if declared.
+    pub fn table(&self, name: &str) -> Option<&DynamoTableConfigs> {
+        self.tables.get(name)
+    }
+
+    /// Validates the endpoint, region, client and table configuration.
+    ///
+    /// The tables must have been loaded without errors.
+    /// The endpoint, when set, must be an `http` or `https` URL with a host, and
+    /// the local emulator mode cannot be used when `env` is `Environment::Prod`. The region
+    /// must be set. At least one attempt is required, timeouts must be positive and
+    /// an attempt cannot outlast its operation. Provisioned capacities must be
+    /// positive. Physical table names must follow the DynamoDB naming rules (3 to 255
+    /// characters among `a-z`, `A-Z`, `0-9`, `_`, `-` and `.`) and be unique, a sort
+    /// key requires a partition key, and the TTL attribute cannot be a key attribute.
+    ///
+    /// ## Returns
+    ///
+    /// `Ok(())` if the configuration is consistent, a `ConfigsError::Invalid` otherwise.
+    pub fn validate(&self) -> Result<(), ConfigsError> {
+        if let Some(e) = &self.tables_error {
+            return Err(e.clone());
+        }
+        if let Some(endpoint) = &self.endpoint {
+            uri::validate_http_url("DynamoDB endpoint", endpoint)?;
+        }
+        if self.local && self.env.is_prod() {
+            return Err(ConfigsError::Invalid(
+                "DynamoDB local mode cannot be used in production".into(),
+            ));
+        }
+        if self.region.is_empty() {
+            return Err(ConfigsError::Missing(DYNAMO_REGION_ENV_KEY.into()));
+        }
+        if self.max_attempts == 0 {
+            return Err(ConfigsError::Invalid(format!(
+                "{} must be at least 1",
+                DYNAMO_MAX_ATTEMPTS_ENV_KEY
+            )));
+        }
+        for (key, timeout) in [
+            (DYNAMO_CONNECT_TIMEOUT_ENV_KEY, self.connect_timeout),
+            (DYNAMO_OPERATION_TIMEOUT_ENV_KEY, self.operation_timeout),
+            (
+                DYNAMO_OPERATION_ATTEMPT_TIMEOUT_ENV_KEY,
+                self.operation_attempt_timeout,
+            ),
+        ] {
+            if timeout.is_some_and(|t| t.is_zero()) {
+                return Err(ConfigsError::Invalid(format!("{} must be positive", key)));
+            }
+        }
+        if let (Some(attempt), Some(operation)) =
+            (self.operation_attempt_timeout, self.operation_timeout)
+            && attempt > operation
+        {
+            return Err(ConfigsError::Invalid(format!(
+                "{} cannot exceed {}",
+                DYNAMO_OPERATION_ATTEMPT_TIMEOUT_ENV_KEY, DYNAMO_OPERATION_TIMEOUT_ENV_KEY
+            )));
+        }
+        self.billing_mode.validate("default")?;
+        if !self.table.is_empty() {
+            validate_table_name(&self.table)?;
+        }
+
+        let mut names = HashSet::new();
+        for (logical, table) in &self.tables {
+            validate_table_name(&table.name)?;
+            if !names.insert(table.name.as_str()) {
+                return Err(ConfigsError::Invalid(format!(
+                    "DynamoDB table \"{}\" is mapped more than once",
+                    table.name
+                )));
+            }
+            if table.sort_key.is_some() && table.partition_key.is_none() {
+                return Err(ConfigsError::Invalid(format!(
+                    "DynamoDB table \"{}\" has a sort key without a partition key",
+                    logical
+                )));
+            }
+            if let Some(billing_mode) = &table.billing_mode {
+                billing_mode.validate(logical)?;
+            }
+            if let Some(attribute) = &table.ttl_attribute {
+                let is_key = [&table.partition_key, &table.sort_key]
+                    .into_iter()
+                    .flatten()
+                    .any(|key| &key.name == attribute);
+                if attribute.is_empty() || is_key {
+                    return Err(ConfigsError::Invalid(format!(
+                        "DynamoDB table \"{}\" has an invalid TTL attribute \"{}\"",
+                        logical, attribute
+                    )));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn env_millis(key: &str) -> Option<Duration> {
+    std::env::var(key)
+        .ok()
+        .and_then(|v| v.parse::<u64>().ok())
+        .map(Duration::from_millis)
+}
+
+fn validate_table_name(name: &str) -> Result<(), ConfigsError> {
+    let valid = (3..=255).contains(&name.len())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'));
+
+    if valid {
+        Ok(())
+    } else {
+        Err(ConfigsError::Invalid(format!(
+            "\"{}\" is not a valid DynamoDB table name",
+            name
+        )))
+    }
+}
+
+impl Default for DynamoConfigs {
+    fn default() -> Self {
+        Self::for_env(&Environment::default())
+    }
+}
+
+/// # DynamoTableConfigs
+///
+/// Configuration of a DynamoDB table.
+///
+/// The key schema is a hint for tooling that creates tables or builds keys;
+/// it is not checked against the deployed table.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DynamoTableConfigs {
+    /// The physical table name, `{env}` is replaced by the environment
+    pub name: String,
+    /// The attribute holding the item expiration time (Default: None)
+    #[serde(default)]
+    pub ttl_attribute: Option<String>,
+    /// The time-to-live for items in seconds (Default: None)
+    #[serde(default)]
+    pub ttl: Option<u64>,
+    /// The partition (hash) key (Default: None)
+    #[serde(default)]
+    pub partition_key: Option<DynamoKeyConfigs>,
+    /// The sort (range) key (Default: None)
+    #[serde(default)]
+    pub sort_key: Option<DynamoKeyConfigs>,
+    /// The billing mode, e.g. `{"mode": "provisioned", "read_capacity": 5, "write_capacity": 5}`
+    /// (Default: None)
+    #[serde(default)]
+    pub billing_mode: Option<DynamoBillingMode>,
+}
+
+/// Capacity units used when provisioned billing does not set them.
+const DYNAMO_DEFAULT_CAPACITY: u64 = 5;
+
+/// # DynamoRetryMode
+///
+/// Enum representing the AWS SDK retry modes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DynamoRetryMode {
+    /// Exponential backoff with a retry quota (default)
+    #[default]
+    Standard,
+    /// Standard retries with client-side rate limiting when throttled
+    Adaptive,
+}
+
+impl From<&str> for DynamoRetryMode {
+    /// Creates a `DynamoRetryMode` from a string slice.
+    ///
+    /// The conversion is case-insensitive. Unknown values return
+    /// `DynamoRetryMode::Standard`.
+    ///
+    /// ## Parameters
+    ///
+    /// * `value` - A string slice containing the retry mode
+    ///
+    /// ## Returns
+    ///
+    /// A `DynamoRetryMode` variant corresponding to the input string
+    fn from(value: &str) -> Self {
+        match value.trim().to_lowercase().as_str() {
+            "adaptive" => Self::Adaptive,
+            _ => Self::Standard,
+        }
+    }
+}
+
+impl Display for DynamoRetryMode {
+    /// Formats the `DynamoRetryMode` as its AWS SDK name.
+    ///
+    /// ## Returns
+    ///
+    /// A string representation of the retry mode (e.g., "adaptive").
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::Standard => write!(f, "standard"),
+            Self::Adaptive => write!(f, "adaptive"),
         }
     }
 }
+
+/// # DynamoBillingMode
+///
+/// Enum representing how a DynamoDB table is billed for reads and writes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum DynamoBillingMode {
+    /// Pay per request, "PAY_PER_REQUEST" (default)
+    #[default]
+    #[serde(alias = "pay_per_request")]
+    OnDemand,
+    /// Provisioned throughput, "PROVISIONED"
+    Provisioned {
+        /// Read capacity units
+        read_capacity: u64,
+        /// Write capacity units
+        write_capacity: u64,
+    },
+}
+
+impl DynamoBillingMode {
+    /// Returns the billing mode as named by the DynamoDB API.
+    ///
+    /// ## Returns
+    ///
+    /// "PAY_PER_REQUEST" or "PROVISIONED".
+    pub fn as_api_str(&self) -> &'static str {
+        match self {
+            Self::OnDemand => "PAY_PER_REQUEST",
+            Self::Provisioned { .. } => "PROVISIONED",
+        }
+    }
+
+    fn validate(&self, table: &str) -> Result<(), ConfigsError> {
+        match self {
+            Self::Provisioned {
+                read_capacity,
+                write_capacity,
+            } if *read_capacity == 0 || *write_capacity == 0 => {
+                Err(ConfigsError::Invalid(format!(
+                    "DynamoDB table \"{}\" needs positive provisioned capacity",
+                    table
+                )))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// # DynamoKeyConfigs
+///
+/// A key attribute of a DynamoDB table.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DynamoKeyConfigs {
+    /// The attribute name
+    pub name: String,
+    /// The attribute type (Default: DynamoAttributeType::String)
+    #[serde(default, rename = "type")]
+    pub kind: DynamoAttributeType,
+}
+
+/// # DynamoAttributeType
+///
+/// Enum representing the scalar types allowed for DynamoDB key attributes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum DynamoAttributeType {
+    /// String attribute, "S" (default)
+    #[default]
+    #[serde(rename = "S", alias = "s", alias = "string")]
+    String,
+    /// Number attribute, "N"
+    #[serde(rename = "N", alias = "n", alias = "number")]
+    Number,
+    /// Binary attribute, "B"
+    #[serde(rename = "B", alias = "b", alias = "binary")]
+    Binary,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TABLES: &str = r#"{
+        "sessions": {
+            "name": "sessions-{env}",
+            "ttl_attribute": "expires_at",
+            "ttl": 3600,
+            "partition_key": {"name": "id", "type": "S"}
+        },
+        "events": {
+            "name": "events-{env}",
+            "billing_mode": {"mode": "provisioned", "read_capacity": 10, "write_capacity": 5},
+            "partition_key": {"name": "device", "type": "S"},
+            "sort_key": {"name": "timestamp", "type": "N"}
+        }
+    }"#;
+
+    #[test]
+    fn should_resolve_tables_for_env() {
+        let cfgs = DynamoConfigs {
+            ttl_attribute: Some("ttl".into()),
+            tables: DynamoConfigs::tables_from_json(TABLES).unwrap(),
+            ..DynamoConfigs::for_env(&Environment::Prod)
+        }
+        .with_env(&Environment::Prod);
+
+        let sessions = cfgs.table("sessions").unwrap();
+        assert_eq!(sessions.name, "sessions-prd");
+        assert_eq!(sessions.ttl_attribute.as_deref(), Some("expires_at"));
+        assert_eq!(sessions.ttl, Some(3600));
+
+        let events = cfgs.table("events").unwrap();
+        assert_eq!(events.name, "events-prd");
+        assert_eq!(events.ttl_attribute.as_deref(), Some("ttl"));
+        assert_eq!(events.ttl, Some(31536000));
+        assert_eq!(
+            events.sort_key.as_ref().map(|k| k.kind),
+            Some(DynamoAttributeType::Number)
+        );
+
+        assert_eq!(
+            events.billing_mode,
+            Some(DynamoBillingMode::Provisioned {
+                read_capacity: 10,
+                write_capacity: 5
+            })
+        );
+        assert_eq!(sessions.billing_mode, Some(DynamoBillingMode::OnDemand));
+
+        assert!(cfgs.table("unknown").is_none());
+        assert_eq!(cfgs.validate(), Ok(()));
+
+        let cfgs = DynamoConfigs {
+            local: true,
+            ..cfgs
+        };
+        assert!(matches!(cfgs.validate(), Err(ConfigsError::Invalid(_))));
+    }
+
+    #[test]
+    fn should_default_endpoint_by_env() {
+        let local = DynamoConfigs::for_env(&Environment::Local);
+        assert!(local.local);
+        assert_eq!(local.endpoint.as_deref(), Some(DYNAMO_LOCAL_ENDPOINT));
+
+        let prod = DynamoConfigs::for_env(&Environment::Prod);
+        assert!(!prod.local);
+        assert_eq!(prod.endpoint, None);
+        assert_eq!(prod.region, AWS_DEFAULT_REGION);
+
+        let cfgs = DynamoConfigs::for_env(&Environment::Local).with_env(&Environment::Prod);
+        assert!(cfgs.env.is_prod());
+        assert!(matches!(cfgs.validate(), Err(ConfigsError::Invalid(_))));
+    }
+
+    #[test]
+    fn should_validate_client_settings() {
+        let cfgs = DynamoConfigs {
+            retry_mode: DynamoRetryMode::from("ADAPTIVE"),
+            operation_timeout: Some(Duration::from_secs(5)),
+            operation_attempt_timeout: Some(Duration::from_secs(2)),
+            ..DynamoConfigs::for_env(&Environment::Local)
+        };
+        assert_eq!(cfgs.retry_mode.to_string(), "adaptive");
+        assert_eq!(cfgs.validate(), Ok(()));
+
+        let cfgs = DynamoConfigs {
+            operation_timeout: Some(Duration::from_secs(1)),
+            operation_attempt_timeout: Some(Duration::from_secs(2)),
+            ..DynamoConfigs::for_env(&Environment::Local)
+        };
+        assert!(matches!(cfgs.validate(), Err(ConfigsError::Invalid(_))));
+
+        let cfgs = DynamoConfigs {
+            max_attempts: 0,
+            ..DynamoConfigs::for_env(&Environment::Local)
+        };
+        assert!(matches!(cfgs.validate(), Err(ConfigsError::Invalid(_))));
+
+        let cfgs = DynamoConfigs {
+            billing_mode: DynamoBillingMode::Provisioned {
+                read_capacity: 0,
+                write_capacity: 5,
+            },
+            ..DynamoConfigs::for_env(&Environment::Local)
+        };
+        assert!(matches!(cfgs.validate(), Err(ConfigsError::Invalid(_))));
+    }
+
+    #[test]
+    fn should_validate_tables() {
+        let mut cfgs = DynamoConfigs {
+            tables: DynamoConfigs::tables_from_json(TABLES).unwrap(),
+            ..DynamoConfigs::for_env(&Environment::Local)
+        }
+        .with_env(&Environment::Local);
+
+        cfgs.tables.get_mut("sessions").unwrap().ttl_attribute = Some("id".into());
+        assert!(matches!(cfgs.validate(), Err(ConfigsError::Invalid(_))));
+
+        cfgs.tables.get_mut("sessions").unwrap().ttl_attribute = None;
+        cfgs.tables.get_mut("events").unwrap().name = "sessions-local".into();
+        assert!(matches!(cfgs.validate(), Err(ConfigsError::Invalid(_))));
+
+        cfgs.tables.get_mut("events").unwrap().name = "events table".into();
+        assert!(matches!(cfgs.validate(), Err(ConfigsError::Invalid(_))));
+
+        let cfgs = DynamoConfigs {
+            tables_error: DynamoConfigs::tables_from_json("{").err(),
+            ..Default::default()
+        };
+        assert!(matches!(cfgs.validate(), Err(ConfigsError::Parse(_))));
+    }
+}
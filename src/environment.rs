@@ -57,6 +57,25 @@ impl Display for Environment {
     }
 }
 
+impl From<&str> for Environment {
+    /// Creates an `Environment` from a string slice.
+    ///
+    /// Accepts the names used in `RUST_ENV` (e.g. "production", "prd", "staging",
+    /// "develop", "dev") in lowercase or uppercase. Any other value returns
+    /// `Environment::Local`; use `Environment::parse` to reject unknown names.
+    ///
+    /// ## Parameters
+    ///
+    /// * `value` - A string slice containing the environment name
+    ///
+    /// ## Returns
+    ///
+    /// The `Environment` corresponding to the input string
+    fn from(value: &str) -> Self {
+        Environment::parse(value).unwrap_or_default()
+    }
+}
+
 impl Environment {
     /// Creates an `Environment` based on the RUST_ENV environment variable.
     ///
@@ -70,11 +89,29 @@ impl Environment {
     pub fn from_rust_env() -> Environment {
         let env = env::var("RUST_ENV").unwrap_or_default();
 
-        match env.as_str() {
-            "production" | "PRODUCTION" | "prod" | "PROD" | "prd" | "PRD" => Environment::Prod,
-            "staging" | "STAGING" | "stg" | "STG" => Environment::Staging,
-            "develop" | "DEVELOP" | "dev" | "DEV" => Environment::Dev,
-            _ => Environment::Local,
+        Environment::from(env.as_str())
+    }
+
+    /// Parses an environment name strictly.
+    ///
+    /// Accepts the same names as `From<&str>`, plus "local", in lowercase or uppercase.
+    ///
+    /// ## Parameters
+    ///
+    /// * `value` - A string slice containing the environment name
+    ///
+    /// ## Returns
+    ///
+    /// The `Environment` corresponding to the input string, or `None` for unknown names.
+    pub fn parse(value: &str) -> Option<Environment> {
+        match value {
+            "production" | "PRODUCTION" | "prod" | "PROD" | "prd" | "PRD" => {
+                Some(Environment::Prod)
+            }
+            "staging" | "STAGING" | "stg" | "STG" => Some(Environment::Staging),
+            "develop" | "DEVELOP" | "dev" | "DEV" => Some(Environment::Dev),
+            "local" | "LOCAL" => Some(Environment::Local),
+            _ => None,
         }
     }
 
@@ -0,0 +1,44 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! Defines the error type returned by configuration validation.
+//!
+//! Loading configuration from the environment never fails: unknown or malformed
+//! values fall back to the defaults. Values where a fallback would be unsafe (e.g.
+//! an environment allow-list with a typo) are kept as errors on the configuration
+//! instead. Validation is an explicit, separate step that reports both, so
+//! applications can decide when to fail fast on an inconsistent configuration.
+
+use thiserror::Error;
+
+/// # ConfigsError
+///
+/// Errors reported when a configuration is validated or parsed.
+///
+/// ## Examples
+///
+/// ```
+/// use configs::postgres::{PostgresConfigs, PostgresSslMode};
+///
+/// let mut pg_config = PostgresConfigs::default();
+/// pg_config.ssl_mode = PostgresSslMode::VerifyFull;
+///
+/// // verify-full requires a CA certificate
+/// assert!(pg_config.validate().is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ConfigsError {
+    /// A required configuration value is missing
+    #[error("missing required config: {0}")]
+    Missing(String),
+    /// A configuration value is present but not acceptable
+    #[error("invalid config: {0}")]
+    Invalid(String),
+    /// A configured file path does not exist
+    #[error("file not found: {0}")]
+    FileNotFound(String),
+    /// A configuration document could not be parsed
+    #[error("failed to parse {0}")]
+    Parse(String),
+}
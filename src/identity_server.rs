@@ -6,6 +6,17 @@
 //!
 //! This module provides configuration options for connecting to and
 //! authenticating with identity servers like Auth0, Keycloak, etc.
+//!
+//! The OpenID Connect endpoints are derived from `url` and `realm` following
+//! the layout of the configured [`IdentityProvider`], so services don't need
+//! to concatenate provider paths themselves. When the provider's discovery
+//! document is shipped with the deployment (e.g. air-gapped environments), the
+//! endpoints it declares take precedence over the derived ones.
+
+use serde::Deserialize;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+use crate::{document, errors::ConfigsError, uri};
 
 /// # IdentityServerConfigs
 ///
@@ -25,15 +36,26 @@
 /// ```
 #[derive(Debug, Clone)]
 pub struct IdentityServerConfigs {
+    /// ENV KEY: "IDENTITY_SERVER_PROVIDER"
+    ///
+    /// Identity provider, defines the endpoint layout (Default: Keycloak)
+    pub provider: IdentityProvider,
+    /// Error raised when "IDENTITY_SERVER_PROVIDER" is not a known provider,
+    /// reported by `validate()` (Default: None)
+    pub provider_error: Option<ConfigsError>,
     /// ENV KEY: "IDENTITY_SERVER_URL"
     ///
     /// Identity Server URL (Default: "")
+    ///
+    /// In Keycloak, the server URL without the realm path. In Auth0, the tenant
+    /// domain URL. In Cognito, the user pool domain URL (hosted UI).
     pub url: String,
     /// ENV KEY: "IDENTITY_SERVER_REALM"
     ///
     /// Identity Application Realm (Default: "")
     ///
-    /// In Auth0, Realm is the same as Domain
+    /// In Auth0, Realm is the same as Domain. In Cognito, Realm is the user pool
+    /// ID, e.g. "eu-west-1_AbCdEfGhI"
     pub realm: String,
     /// ENV KEY: "IDENTITY_SERVER_AUDIENCE"
     ///
@@ -51,12 +73,115 @@ pub struct IdentityServerConfigs {
     ///
     /// OAuth client secret (Default: "")
     pub client_secret: String,
+    /// ENV KEY: "IDENTITY_SERVER_TOKEN_ENDPOINT_AUTH_METHOD"
+    ///
+    /// How the client authenticates to the token endpoint; `client_secret` is only
+    /// required by the secret based methods (Default: ClientSecretBasic)
+    pub token_endpoint_auth_method: TokenEndpointAuthMethod,
+    /// Error raised when "IDENTITY_SERVER_TOKEN_ENDPOINT_AUTH_METHOD" holds an unknown
+    /// method, reported by `validate()` (Default: None)
+    pub token_endpoint_auth_method_error: Option<ConfigsError>,
     /// ENV KEY: "IDENTITY_SERVER_GRANT_TYPE"
     ///
-    /// OAuth grant type (Default: "client_credentials")
-    pub grant_type: String,
+    /// OAuth grant type (Default: ClientCredentials)
+    pub grant_type: GrantType,
+    /// Error raised when "IDENTITY_SERVER_GRANT_TYPE" holds an unknown grant type,
+    /// reported by `validate()` (Default: None)
+    pub grant_type_error: Option<ConfigsError>,
+    /// ENV KEY: "IDENTITY_SERVER_SCOPES"
+    ///
+    /// Scopes to request, separated by spaces or commas (Default: empty)
+    pub scopes: Vec<String>,
+    /// ENV KEY: "IDENTITY_SERVER_RESOURCES"
+    ///
+    /// Resource indicators (RFC 8707) to request, separated by commas (Default: empty)
+    pub resources: Vec<String>,
+    /// ENV KEY: "IDENTITY_SERVER_REDIRECT_URI"
+    ///
+    /// Redirect URI registered for the authorization code grant (Default: "")
+    pub redirect_uri: String,
+    /// ENV KEY: "IDENTITY_SERVER_PKCE"
+    ///
+    /// Whether the authorization code grant uses PKCE with the S256 method (Default: true)
+    pub pkce: bool,
+    /// ENV KEY: "IDENTITY_SERVER_USERNAME"
+    ///
+    /// Resource owner username for the password grant (Default: "")
+    pub username: String,
+    /// ENV KEY: "IDENTITY_SERVER_PASSWORD"
+    ///
+    /// Resource owner password for the password grant (Default: "")
+    pub password: String,
+    /// ENV KEY: "IDENTITY_SERVER_REFRESH_TOKEN"
+    ///
+    /// Refresh token for the refresh token grant (Default: "")
+    pub refresh_token: String,
+    /// ENV KEY: "IDENTITY_SERVER_SUBJECT_TOKEN"
+    ///
+    /// Token to exchange for the token exchange grant (Default: "")
+    pub subject_token: String,
+    /// ENV KEY: "IDENTITY_SERVER_SUBJECT_TOKEN_TYPE"
+    ///
+    /// Type of the token to exchange (Default: "urn:ietf:params:oauth:token-type:access_token")
+    pub subject_token_type: String,
+    /// ENV KEY: "IDENTITY_SERVER_ASSERTION"
+    ///
+    /// Signed JWT for the JWT bearer grant (Default: "")
+    pub assertion: String,
+    /// ENV KEY: "IDENTITY_SERVER_DISCOVERY_FILE" or "IDENTITY_SERVER_DISCOVERY"
+    ///
+    /// OpenID Connect discovery document, loaded from a file or from inline JSON
+    /// (Default: None)
+    pub discovery: Option<IdentityServerDiscovery>,
+    /// Error raised when the discovery document file cannot be read or the
+    /// document cannot be parsed, reported by `validate()` (Default: None)
+    pub discovery_error: Option<ConfigsError>,
+}
+
+/// # IdentityServerDiscovery
+///
+/// The provider metadata published at `.well-known/openid-configuration`.
+///
+/// Only the metadata used by this crate is kept; unknown fields are ignored.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct IdentityServerDiscovery {
+    /// The issuer the provider puts in the tokens
+    pub issuer: String,
+    /// OAuth authorization endpoint
+    pub authorization_endpoint: Option<String>,
+    /// OAuth token endpoint
+    pub token_endpoint: Option<String>,
+    /// OpenID Connect userinfo endpoint
+    pub userinfo_endpoint: Option<String>,
+    /// JSON Web Key Set URL
+    pub jwks_uri: Option<String>,
+    /// Token introspection endpoint (RFC 7662)
+    pub introspection_endpoint: Option<String>,
+    /// Token revocation endpoint (RFC 7009)
+    pub revocation_endpoint: Option<String>,
+    /// Logout (end session) endpoint
+    pub end_session_endpoint: Option<String>,
+    /// Device authorization endpoint (RFC 8628)
+    pub device_authorization_endpoint: Option<String>,
+    /// Scopes the provider supports (Default: empty)
+    #[serde(default)]
+    pub scopes_supported: Vec<String>,
+    /// Grant types the provider supports
+    /// (Default: "authorization_code" and "implicit", as defined by the specification)
+    #[serde(default = "default_grant_types_supported")]
+    pub grant_types_supported: Vec<String>,
+    /// Algorithms the provider signs ID tokens with (Default: empty)
+    #[serde(default)]
+    pub id_token_signing_alg_values_supported: Vec<String>,
+    /// Client authentication methods the token endpoint accepts (Default: empty)
+    #[serde(default)]
+    pub token_endpoint_auth_methods_supported: Vec<String>,
+    /// PKCE code challenge methods the provider supports (Default: empty)
+    #[serde(default)]
+    pub code_challenge_methods_supported: Vec<String>,
 }
 
+pub const IDENTITY_SERVER_PROVIDER_ENV_KEY: &str = "IDENTITY_SERVER_PROVIDER";
 pub const IDENTITY_SERVER_URL_ENV_KEY: &str = "IDENTITY_SERVER_URL";
 pub const IDENTITY_SERVER_REALM_ENV_KEY: &str = "IDENTITY_SERVER_REALM";
 pub const IDENTITY_SERVER_AUDIENCE_ENV_KEY: &str = "IDENTITY_SERVER_AUDIENCE";
@@ -64,6 +189,25 @@ pub const IDENTITY_SERVER_ISSUER_ENV_KEY: &str = "IDENTITY_SERVER_ISSUER";
 pub const IDENTITY_SERVER_GRANT_TYPE_ENV_KEY: &str = "IDENTITY_SERVER_GRANT_TYPE";
 pub const IDENTITY_SERVER_CLIENT_ID_ENV_KEY: &str = "IDENTITY_SERVER_CLIENT_ID";
 pub const IDENTITY_SERVER_CLIENT_SECRET_ENV_KEY: &str = "IDENTITY_SERVER_CLIENT_SECRET";
+pub const IDENTITY_SERVER_TOKEN_ENDPOINT_AUTH_METHOD_ENV_KEY: &str =
+    "IDENTITY_SERVER_TOKEN_ENDPOINT_AUTH_METHOD";
+pub const IDENTITY_SERVER_DISCOVERY_FILE_ENV_KEY: &str = "IDENTITY_SERVER_DISCOVERY_FILE";
+pub const IDENTITY_SERVER_DISCOVERY_ENV_KEY: &str = "IDENTITY_SERVER_DISCOVERY";
+pub const IDENTITY_SERVER_SCOPES_ENV_KEY: &str = "IDENTITY_SERVER_SCOPES";
+pub const IDENTITY_SERVER_RESOURCES_ENV_KEY: &str = "IDENTITY_SERVER_RESOURCES";
+pub const IDENTITY_SERVER_REDIRECT_URI_ENV_KEY: &str = "IDENTITY_SERVER_REDIRECT_URI";
+pub const IDENTITY_SERVER_PKCE_ENV_KEY: &str = "IDENTITY_SERVER_PKCE";
+pub const IDENTITY_SERVER_USERNAME_ENV_KEY: &str = "IDENTITY_SERVER_USERNAME";
+pub const IDENTITY_SERVER_PASSWORD_ENV_KEY: &str = "IDENTITY_SERVER_PASSWORD";
+pub const IDENTITY_SERVER_REFRESH_TOKEN_ENV_KEY: &str = "IDENTITY_SERVER_REFRESH_TOKEN";
+pub const IDENTITY_SERVER_SUBJECT_TOKEN_ENV_KEY: &str = "IDENTITY_SERVER_SUBJECT_TOKEN";
+pub const IDENTITY_SERVER_SUBJECT_TOKEN_TYPE_ENV_KEY: &str = "IDENTITY_SERVER_SUBJECT_TOKEN_TYPE";
+pub const IDENTITY_SERVER_ASSERTION_ENV_KEY: &str = "IDENTITY_SERVER_ASSERTION";
+
+/// Subject token type of OAuth access tokens (RFC 8693).
+pub const ACCESS_TOKEN_TYPE: &str = "urn:ietf:params:oauth:token-type:access_token";
+/// PKCE code challenge method used for the authorization code grant.
+const PKCE_METHOD: &str = "S256";
 
 impl IdentityServerConfigs {
     /// Creates a new `IdentityServerConfigs` instance from environment variables.
@@ -77,6 +221,17 @@ impl IdentityServerConfigs {
     pub fn new() -> Self {
         let mut cfgs = Self::default();
 
+        if let Ok(value) = std::env::var(IDENTITY_SERVER_PROVIDER_ENV_KEY) {
+            match IdentityProvider::parse(&value) {
+                Some(provider) => cfgs.provider = provider,
+                None => {
+                    cfgs.provider_error = Some(ConfigsError::Invalid(format!(
+                        "{} \"{}\" is not a known provider",
+                        IDENTITY_SERVER_PROVIDER_ENV_KEY, value
+                    )))
+                }
+            }
+        }
         cfgs.url = std::env::var(IDENTITY_SERVER_URL_ENV_KEY).unwrap_or(cfgs.url);
         cfgs.realm = std::env::var(IDENTITY_SERVER_REALM_ENV_KEY).unwrap_or(cfgs.realm);
         cfgs.audience = std::env::var(IDENTITY_SERVER_AUDIENCE_ENV_KEY).unwrap_or(cfgs.audience);
@@ -84,23 +239,1208 @@ impl IdentityServerConfigs {
         cfgs.client_id = std::env::var(IDENTITY_SERVER_CLIENT_ID_ENV_KEY).unwrap_or(cfgs.client_id);
         cfgs.client_secret =
             std::env::var(IDENTITY_SERVER_CLIENT_SECRET_ENV_KEY).unwrap_or(cfgs.client_secret);
-        cfgs.grant_type =
-            std::env::var(IDENTITY_SERVER_GRANT_TYPE_ENV_KEY).unwrap_or(cfgs.grant_type);
+        if let Ok(value) = std::env::var(IDENTITY_SERVER_GRANT_TYPE_ENV_KEY) {
+            match GrantType::parse(&value) {
+                Some(grant_type) => cfgs.grant_type = grant_type,
+                None => {
+                    cfgs.grant_type_error = Some(ConfigsError::Invalid(format!(
+                        "{} \"{}\" is not a supported grant type",
+                        IDENTITY_SERVER_GRANT_TYPE_ENV_KEY, value
+                    )))
+                }
+            }
+        }
+        if let Ok(value) = std::env::var(IDENTITY_SERVER_TOKEN_ENDPOINT_AUTH_METHOD_ENV_KEY) {
+            match TokenEndpointAuthMethod::parse(&value) {
+                Some(method) => cfgs.token_endpoint_auth_method = method,
+                None => {
+                    cfgs.token_endpoint_auth_method_error = Some(ConfigsError::Invalid(format!(
+                        "{} \"{}\" is not a supported authentication method",
+                        IDENTITY_SERVER_TOKEN_ENDPOINT_AUTH_METHOD_ENV_KEY, value
+                    )))
+                }
+            }
+        }
+        cfgs.scopes = std::env::var(IDENTITY_SERVER_SCOPES_ENV_KEY)
+            .map(|value| split_list(&value, |c| c == ',' || c.is_whitespace()))
+            .unwrap_or(cfgs.scopes);
+        cfgs.resources = std::env::var(IDENTITY_SERVER_RESOURCES_ENV_KEY)
+            .map(|value| split_list(&value, |c| c == ','))
+            .unwrap_or(cfgs.resources);
+        cfgs.redirect_uri =
+            std::env::var(IDENTITY_SERVER_REDIRECT_URI_ENV_KEY).unwrap_or(cfgs.redirect_uri);
+        cfgs.pkce = std::env::var(IDENTITY_SERVER_PKCE_ENV_KEY)
+            .ok()
+            .and_then(|v| v.parse::<bool>().ok())
+            .unwrap_or(cfgs.pkce);
+        cfgs.username = std::env::var(IDENTITY_SERVER_USERNAME_ENV_KEY).unwrap_or(cfgs.username);
+        cfgs.password = std::env::var(IDENTITY_SERVER_PASSWORD_ENV_KEY).unwrap_or(cfgs.password);
+        cfgs.refresh_token =
+            std::env::var(IDENTITY_SERVER_REFRESH_TOKEN_ENV_KEY).unwrap_or(cfgs.refresh_token);
+        cfgs.subject_token =
+            std::env::var(IDENTITY_SERVER_SUBJECT_TOKEN_ENV_KEY).unwrap_or(cfgs.subject_token);
+        cfgs.subject_token_type = std::env::var(IDENTITY_SERVER_SUBJECT_TOKEN_TYPE_ENV_KEY)
+            .unwrap_or(cfgs.subject_token_type);
+        cfgs.assertion = std::env::var(IDENTITY_SERVER_ASSERTION_ENV_KEY).unwrap_or(cfgs.assertion);
+        let discovery = std::env::var(IDENTITY_SERVER_DISCOVERY_FILE_ENV_KEY)
+            .ok()
+            .map(|path| IdentityServerDiscovery::from_file(&path))
+            .or_else(|| {
+                std::env::var(IDENTITY_SERVER_DISCOVERY_ENV_KEY)
+                    .ok()
+                    .map(|json| IdentityServerDiscovery::from_json(&json))
+            });
+        match discovery {
+            Some(Ok(discovery)) => cfgs.discovery = Some(discovery),
+            Some(Err(e)) => cfgs.discovery_error = Some(e),
+            None => {}
+        }
 
         cfgs
     }
+
+    /// Returns the issuer derived from `url` and `realm`.
+    ///
+    /// ## Returns
+    ///
+    /// The issuer the provider puts in the tokens, or `None` for the generic
+    /// provider or when the fields it is derived from are not set.
+    pub fn derived_issuer(&self) -> Option<String> {
+        match self.provider {
+            IdentityProvider::Keycloak => self.keycloak_realm_url(),
+            IdentityProvider::Auth0 => self.auth0_domain_url().map(|url| format!("{}/", url)),
+            IdentityProvider::Cognito => {
+                let region = cognito_region(&self.realm)?;
+                Some(format!(
+                    "https://cognito-idp.{}.amazonaws.com/{}",
+                    region,
+                    uri::encode(&self.realm)
+                ))
+            }
+            IdentityProvider::Generic => None,
+        }
+    }
+
+    /// Returns the URL of the OpenID Connect discovery document.
+    ///
+    /// For the generic provider it is derived from `issuer`, falling back to `url`.
+    ///
+    /// ## Returns
+    ///
+    /// The `.well-known/openid-configuration` URL, or `None` if the issuer can't be derived.
+    pub fn discovery_endpoint(&self) -> Option<String> {
+        let issuer = match self.provider {
+            IdentityProvider::Generic if !self.issuer.is_empty() => self.issuer.clone(),
+            IdentityProvider::Generic if !self.url.is_empty() => self.url.clone(),
+            IdentityProvider::Generic => return None,
+            _ => self.derived_issuer()?,
+        };
+
+        Some(format!(
+            "{}/.well-known/openid-configuration",
+            issuer.trim_end_matches('/')
+        ))
+    }
+
+    /// Returns the OAuth token endpoint.
+    ///
+    /// This and the other endpoint accessors return the endpoint declared by the
+    /// discovery document when one is loaded, or the one derived from the provider layout.
+    pub fn token_endpoint(&self) -> Option<String> {
+        self.endpoint(Endpoint::Token)
+    }
+
+    /// Returns the OAuth authorization endpoint.
+    pub fn authorization_endpoint(&self) -> Option<String> {
+        self.endpoint(Endpoint::Authorization)
+    }
+
+    /// Returns the OpenID Connect userinfo endpoint.
+    pub fn userinfo_endpoint(&self) -> Option<String> {
+        self.endpoint(Endpoint::Userinfo)
+    }
+
+    /// Returns the URL of the JSON Web Key Set used to verify token signatures.
+    pub fn jwks_uri(&self) -> Option<String> {
+        self.endpoint(Endpoint::Jwks)
+    }
+
+    /// Returns the token introspection endpoint (RFC 7662).
+    ///
+    /// Auth0 and Cognito don't expose one, so it is `None` for them.
+    pub fn introspection_endpoint(&self) -> Option<String> {
+        self.endpoint(Endpoint::Introspection)
+    }
+
+    /// Returns the token revocation endpoint (RFC 7009).
+    pub fn revocation_endpoint(&self) -> Option<String> {
+        self.endpoint(Endpoint::Revocation)
+    }
+
+    /// Returns the logout (end session) endpoint.
+    pub fn end_session_endpoint(&self) -> Option<String> {
+        self.endpoint(Endpoint::EndSession)
+    }
+
+    /// Returns the device authorization endpoint (RFC 8628).
+    ///
+    /// Cognito doesn't support the device code grant, so it is `None` for it.
+    pub fn device_authorization_endpoint(&self) -> Option<String> {
+        self.endpoint(Endpoint::DeviceAuthorization)
+    }
+
+    /// Returns the space separated `scope` parameter.
+    pub fn scope(&self) -> String {
+        self.scopes.join(" ")
+    }
+
+    /// Returns the token request parameters of the configured grant.
+    ///
+    /// Client authentication is not included, as it depends on the method the
+    /// client uses, and neither are the values only known at runtime (the
+    /// authorization code, the PKCE verifier or the device code).
+    ///
+    /// ## Returns
+    ///
+    /// The `grant_type`, the grant specific parameters, and the `scope`, `audience`
+    /// and `resource` parameters when they are set.
+    pub fn token_parameters(&self) -> Vec<(&'static str, String)> {
+        let mut params = vec![("grant_type", self.grant_type.as_str().to_owned())];
+
+        match self.grant_type {
+            GrantType::AuthorizationCode => {
+                params.push(("redirect_uri", self.redirect_uri.clone()));
+            }
+            GrantType::Password => {
+                params.push(("username", self.username.clone()));
+                params.push(("password", self.password.clone()));
+            }
+            GrantType::RefreshToken => {
+                params.push(("refresh_token", self.refresh_token.clone()));
+            }
+            GrantType::TokenExchange => {
+                params.push(("subject_token", self.subject_token.clone()));
+                params.push(("subject_token_type", self.subject_token_type.clone()));
+            }
+            GrantType::JwtBearer => {
+                params.push(("assertion", self.assertion.clone()));
+            }
+            GrantType::ClientCredentials | GrantType::DeviceCode => {}
+        }
+
+        if !self.scopes.is_empty() {
+            params.push(("scope", self.scope()));
+        }
+        if !self.audience.is_empty() {
+            params.push(("audience", self.audience.clone()));
+        }
+        for resource in &self.resources {
+            params.push(("resource", resource.clone()));
+        }
+
+        params
+    }
+
+    /// Validates the identity server configuration.
+    ///
+    /// Checks that the provider is known, that the fields its layout needs are set,
+    /// that the URLs are http(s) URLs and that `issuer`, when set, matches the
+    /// derived issuer. The discovery document must have been loaded without errors.
+    /// When a discovery document is loaded, its issuer must match too and the
+    /// provider must support `grant_type` and `token_endpoint_auth_method`.
+    /// Finally, checks that the grant type and authentication method are known and
+    /// that the fields they need are set.
+    ///
+    /// ## Returns
+    ///
+    /// `Ok(())` if the configuration is consistent, a `ConfigsError` otherwise.
+    pub fn validate(&self) -> Result<(), ConfigsError> {
+        if let Some(e) = &self.provider_error {
+            return Err(e.clone());
+        }
+        if let Some(e) = &self.discovery_error {
+            return Err(e.clone());
+        }
+
+        let required: &[(&str, &str)] = match self.provider {
+            IdentityProvider::Keycloak => &[
+                (IDENTITY_SERVER_URL_ENV_KEY, &self.url),
+                (IDENTITY_SERVER_REALM_ENV_KEY, &self.realm),
+            ],
+            IdentityProvider::Cognito => &[(IDENTITY_SERVER_REALM_ENV_KEY, &self.realm)],
+            IdentityProvider::Auth0 | IdentityProvider::Generic => &[],
+        };
+        if let Some((key, _)) = required.iter().find(|(_, value)| value.is_empty()) {
+            return Err(ConfigsError::Missing(format!(
+                "{} ({})",
+                key, self.provider
+            )));
+        }
+
+        match self.provider {
+            IdentityProvider::Auth0 if self.url.is_empty() && self.realm.is_empty() => {
+                return Err(ConfigsError::Missing(format!(
+                    "{} or {} ({})",
+                    IDENTITY_SERVER_URL_ENV_KEY, IDENTITY_SERVER_REALM_ENV_KEY, self.provider
+                )));
+            }
+            IdentityProvider::Generic if self.url.is_empty() && self.issuer.is_empty() => {
+                return Err(ConfigsError::Missing(format!(
+                    "{} or {} ({})",
+                    IDENTITY_SERVER_URL_ENV_KEY, IDENTITY_SERVER_ISSUER_ENV_KEY, self.provider
+                )));
+            }
+            IdentityProvider::Cognito if cognito_region(&self.realm).is_none() => {
+                return Err(ConfigsError::Invalid(format!(
+                    "{} \"{}\" is not a Cognito user pool ID, e.g. \"eu-west-1_AbCdEfGhI\"",
+                    IDENTITY_SERVER_REALM_ENV_KEY, self.realm
+                )));
+            }
+            _ => {}
+        }
+
+        if !self.url.is_empty() {
+            uri::validate_http_url(IDENTITY_SERVER_URL_ENV_KEY, &self.url)?;
+        }
+
+        if let Some(derived) = self.derived_issuer() {
+            uri::validate_http_url("derived issuer", &derived)?;
+            if !self.issuer.is_empty() && self.issuer != derived {
+                return Err(ConfigsError::Invalid(format!(
+                    "{} \"{}\" does not match the {} issuer \"{}\"",
+                    IDENTITY_SERVER_ISSUER_ENV_KEY, self.issuer, self.provider, derived
+                )));
+            }
+        } else if !self.issuer.is_empty() {
+            uri::validate_http_url(IDENTITY_SERVER_ISSUER_ENV_KEY, &self.issuer)?;
+        }
+
+        if let Some(discovery) = &self.discovery {
+            discovery.validate()?;
+
+            let expected = self
+                .derived_issuer()
+                .or_else(|| Some(self.issuer.clone()).filter(|issuer| !issuer.is_empty()));
+            if let Some(expected) = expected
+                && discovery.issuer != expected
+            {
+                return Err(ConfigsError::Invalid(format!(
+                    "discovery document issuer \"{}\" does not match \"{}\"",
+                    discovery.issuer, expected
+                )));
+            }
+
+            if !discovery.supports_grant_type(self.grant_type.as_str()) {
+                return Err(ConfigsError::Invalid(format!(
+                    "{} \"{}\" is not supported by the provider, supported: {}",
+                    IDENTITY_SERVER_GRANT_TYPE_ENV_KEY,
+                    self.grant_type,
+                    discovery.grant_types_supported.join(", ")
+                )));
+            }
+
+            let method = self.token_endpoint_auth_method.as_str();
+            if !discovery.token_endpoint_auth_methods_supported.is_empty()
+                && !discovery
+                    .token_endpoint_auth_methods_supported
+                    .iter()
+                    .any(|supported| supported == method)
+            {
+                return Err(ConfigsError::Invalid(format!(
+                    "{} \"{}\" is not supported by the provider, supported: {}",
+                    IDENTITY_SERVER_TOKEN_ENDPOINT_AUTH_METHOD_ENV_KEY,
+                    method,
+                    discovery.token_endpoint_auth_methods_supported.join(", ")
+                )));
+            }
+
+            if self.grant_type == GrantType::AuthorizationCode
+                && self.pkce
+                && !discovery.code_challenge_methods_supported.is_empty()
+                && !discovery
+                    .code_challenge_methods_supported
+                    .iter()
+                    .any(|method| method == PKCE_METHOD)
+            {
+                return Err(ConfigsError::Invalid(format!(
+                    "the provider doesn't support the {} PKCE method",
+                    PKCE_METHOD
+                )));
+            }
+        }
+
+        self.validate_grant()
+    }
+
+    /// Checks that the fields the configured grant needs are set.
+    ///
+    /// The client secret is only required by the client credentials grant with a
+    /// secret based authentication method; `private_key_jwt` clients sign their
+    /// assertion with a key held by the client library.
+    fn validate_grant(&self) -> Result<(), ConfigsError> {
+        if let Some(e) = self
+            .grant_type_error
+            .as_ref()
+            .or(self.token_endpoint_auth_method_error.as_ref())
+        {
+            return Err(e.clone());
+        }
+
+        let uses_secret = self.token_endpoint_auth_method.uses_secret();
+        let required: &[(&str, &str)] = match self.grant_type {
+            GrantType::ClientCredentials if uses_secret => &[
+                (IDENTITY_SERVER_CLIENT_ID_ENV_KEY, &self.client_id),
+                (IDENTITY_SERVER_CLIENT_SECRET_ENV_KEY, &self.client_secret),
+            ],
+            GrantType::ClientCredentials => &[(IDENTITY_SERVER_CLIENT_ID_ENV_KEY, &self.client_id)],
+            GrantType::AuthorizationCode => &[
+                (IDENTITY_SERVER_CLIENT_ID_ENV_KEY, &self.client_id),
+                (IDENTITY_SERVER_REDIRECT_URI_ENV_KEY, &self.redirect_uri),
+            ],
+            GrantType::Password => &[
+                (IDENTITY_SERVER_CLIENT_ID_ENV_KEY, &self.client_id),
+                (IDENTITY_SERVER_USERNAME_ENV_KEY, &self.username),
+                (IDENTITY_SERVER_PASSWORD_ENV_KEY, &self.password),
+            ],
+            GrantType::RefreshToken => &[
+                (IDENTITY_SERVER_CLIENT_ID_ENV_KEY, &self.client_id),
+                (IDENTITY_SERVER_REFRESH_TOKEN_ENV_KEY, &self.refresh_token),
+            ],
+            GrantType::DeviceCode => &[(IDENTITY_SERVER_CLIENT_ID_ENV_KEY, &self.client_id)],
+            GrantType::TokenExchange => &[
+                (IDENTITY_SERVER_SUBJECT_TOKEN_ENV_KEY, &self.subject_token),
+                (
+                    IDENTITY_SERVER_SUBJECT_TOKEN_TYPE_ENV_KEY,
+                    &self.subject_token_type,
+                ),
+            ],
+            GrantType::JwtBearer => &[(IDENTITY_SERVER_ASSERTION_ENV_KEY, &self.assertion)],
+        };
+        if let Some((key, _)) = required.iter().find(|(_, value)| value.is_empty()) {
+            return Err(ConfigsError::Missing(format!(
+                "{} ({} grant)",
+                key, self.grant_type
+            )));
+        }
+
+        let confidential = match self.token_endpoint_auth_method {
+            TokenEndpointAuthMethod::PrivateKeyJwt => true,
+            TokenEndpointAuthMethod::Public => false,
+            _ => !self.client_secret.is_empty(),
+        };
+
+        match self.grant_type {
+            GrantType::ClientCredentials if !confidential => Err(ConfigsError::Invalid(format!(
+                "the {} grant requires an authenticated client",
+                self.grant_type
+            ))),
+            GrantType::AuthorizationCode if !self.redirect_uri.contains(":/") => {
+                Err(ConfigsError::Invalid(format!(
+                    "{} \"{}\" is not an absolute URI",
+                    IDENTITY_SERVER_REDIRECT_URI_ENV_KEY, self.redirect_uri
+                )))
+            }
+            GrantType::AuthorizationCode if !self.pkce && !confidential => {
+                Err(ConfigsError::Invalid(format!(
+                    "public clients (without {} or private_key_jwt) must use PKCE",
+                    IDENTITY_SERVER_CLIENT_SECRET_ENV_KEY
+                )))
+            }
+            GrantType::DeviceCode if self.device_authorization_endpoint().is_none() => {
+                Err(ConfigsError::Invalid(format!(
+                    "{} doesn't provide a device authorization endpoint",
+                    self.provider
+                )))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns the endpoint declared by the discovery document, or the derived one.
+    fn endpoint(&self, endpoint: Endpoint) -> Option<String> {
+        self.discovery
+            .as_ref()
+            .and_then(|discovery| discovery.endpoint(endpoint))
+            .map(str::to_owned)
+            .or_else(|| self.derived_endpoint(endpoint))
+    }
+
+    /// Derives an endpoint following the provider layout.
+    fn derived_endpoint(&self, endpoint: Endpoint) -> Option<String> {
+        match self.provider {
+            IdentityProvider::Keycloak => {
+                let path = match endpoint {
+                    Endpoint::Token => "token",
+                    Endpoint::Authorization => "auth",
+                    Endpoint::Userinfo => "userinfo",
+                    Endpoint::Jwks => "certs",
+                    Endpoint::Introspection => "token/introspect",
+                    Endpoint::Revocation => "revoke",
+                    Endpoint::EndSession => "logout",
+                    Endpoint::DeviceAuthorization => "auth/device",
+                };
+                let realm = self.keycloak_realm_url()?;
+                Some(format!("{}/protocol/openid-connect/{}", realm, path))
+            }
+            IdentityProvider::Auth0 => {
+                let path = match endpoint {
+                    Endpoint::Token => "/oauth/token",
+                    Endpoint::Authorization => "/authorize",
+                    Endpoint::Userinfo => "/userinfo",
+                    Endpoint::Jwks => "/.well-known/jwks.json",
+                    Endpoint::Introspection => return None,
+                    Endpoint::Revocation => "/oauth/revoke",
+                    Endpoint::EndSession => "/oidc/logout",
+                    Endpoint::DeviceAuthorization => "/oauth/device/code",
+                };
+                Some(format!("{}{}", self.auth0_domain_url()?, path))
+            }
+            IdentityProvider::Cognito => {
+                let path = match endpoint {
+                    Endpoint::Jwks => {
+                        return Some(format!("{}/.well-known/jwks.json", self.derived_issuer()?));
+                    }
+                    Endpoint::Token => "/oauth2/token",
+                    Endpoint::Authorization => "/oauth2/authorize",
+                    Endpoint::Userinfo => "/oauth2/userInfo",
+                    Endpoint::Introspection | Endpoint::DeviceAuthorization => return None,
+                    Endpoint::Revocation => "/oauth2/revoke",
+                    Endpoint::EndSession => "/logout",
+                };
+                if self.url.is_empty() {
+                    return None;
+                }
+                Some(format!("{}{}", self.url.trim_end_matches('/'), path))
+            }
+            IdentityProvider::Generic => None,
+        }
+    }
+
+    /// Returns the Keycloak realm URL, `{url}/realms/{realm}`, with the realm percent-encoded.
+    fn keycloak_realm_url(&self) -> Option<String> {
+        if self.url.is_empty() || self.realm.is_empty() {
+            return None;
+        }
+
+        Some(format!(
+            "{}/realms/{}",
+            self.url.trim_end_matches('/'),
+            uri::encode(&self.realm)
+        ))
+    }
+
+    /// Returns the Auth0 tenant URL from `url`, or from the `realm` domain.
+    fn auth0_domain_url(&self) -> Option<String> {
+        if !self.url.is_empty() {
+            Some(self.url.trim_end_matches('/').to_owned())
+        } else if !self.realm.is_empty() {
+            Some(format!("https://{}", self.realm.trim_end_matches('/')))
+        } else {
+            None
+        }
+    }
+}
+
+/// Returns the region prefix of a Cognito user pool ID, e.g. "eu-west-1".
+fn cognito_region(pool_id: &str) -> Option<&str> {
+    let (region, id) = pool_id.split_once('_')?;
+    let valid = !region.is_empty()
+        && !id.is_empty()
+        && region
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+        && id.chars().all(|c| c.is_ascii_alphanumeric());
+
+    if valid { Some(region) } else { None }
+}
+
+/// Splits a list, dropping empty items.
+fn split_list(value: &str, separator: impl Fn(char) -> bool) -> Vec<String> {
+    value
+        .split(separator)
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(str::to_owned)
+        .collect()
+}
+
+/// The grant types a provider supports when its discovery document omits them.
+fn default_grant_types_supported() -> Vec<String> {
+    vec!["authorization_code".to_owned(), "implicit".to_owned()]
+}
+
+/// OpenID Connect endpoints, derived from the provider layout or discovered.
+#[derive(Debug, Clone, Copy)]
+enum Endpoint {
+    Token,
+    Authorization,
+    Userinfo,
+    Jwks,
+    Introspection,
+    Revocation,
+    EndSession,
+    DeviceAuthorization,
+}
+
+impl Endpoint {
+    const ALL: [Endpoint; 8] = [
+        Endpoint::Token,
+        Endpoint::Authorization,
+        Endpoint::Userinfo,
+        Endpoint::Jwks,
+        Endpoint::Introspection,
+        Endpoint::Revocation,
+        Endpoint::EndSession,
+        Endpoint::DeviceAuthorization,
+    ];
+
+    /// Returns the discovery document metadata name of the endpoint.
+    fn name(self) -> &'static str {
+        match self {
+            Endpoint::Token => "token_endpoint",
+            Endpoint::Authorization => "authorization_endpoint",
+            Endpoint::Userinfo => "userinfo_endpoint",
+            Endpoint::Jwks => "jwks_uri",
+            Endpoint::Introspection => "introspection_endpoint",
+            Endpoint::Revocation => "revocation_endpoint",
+            Endpoint::EndSession => "end_session_endpoint",
+            Endpoint::DeviceAuthorization => "device_authorization_endpoint",
+        }
+    }
+}
+
+impl IdentityServerDiscovery {
+    /// Parses a discovery document from JSON.
+    ///
+    /// ## Parameters
+    ///
+    /// * `json` - The `.well-known/openid-configuration` document
+    ///
+    /// ## Returns
+    ///
+    /// The parsed `IdentityServerDiscovery`, or a `ConfigsError::Parse`.
+    pub fn from_json(json: &str) -> Result<Self, ConfigsError> {
+        document::from_json("OIDC discovery document", json)
+    }
+
+    /// Reads a discovery document from a file.
+    ///
+    /// ## Parameters
+    ///
+    /// * `path` - Path to the saved `.well-known/openid-configuration` document
+    ///
+    /// ## Returns
+    ///
+    /// The parsed `IdentityServerDiscovery`, or a `ConfigsError` if the file cannot be read or parsed.
+    pub fn from_file(path: &str) -> Result<Self, ConfigsError> {
+        document::from_file("OIDC discovery document", path)
+    }
+
+    /// Checks whether the provider supports a grant type.
+    ///
+    /// ## Parameters
+    ///
+    /// * `grant_type` - The OAuth grant type, e.g. "client_credentials"
+    ///
+    /// ## Returns
+    ///
+    /// `true` if the grant type is listed in `grant_types_supported`.
+    pub fn supports_grant_type(&self, grant_type: &str) -> bool {
+        self.grant_types_supported
+            .iter()
+            .any(|supported| supported == grant_type)
+    }
+
+    /// Validates that the issuer and the declared endpoints are http(s) URLs.
+    ///
+    /// ## Returns
+    ///
+    /// `Ok(())` if the document is consistent, a `ConfigsError` otherwise.
+    pub fn validate(&self) -> Result<(), ConfigsError> {
+        uri::validate_http_url("discovery document issuer", &self.issuer)?;
+
+        for endpoint in Endpoint::ALL {
+            if let Some(url) = self.endpoint(endpoint) {
+                uri::validate_http_url(&format!("discovery document {}", endpoint.name()), url)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns a declared endpoint.
+    fn endpoint(&self, endpoint: Endpoint) -> Option<&str> {
+        match endpoint {
+            Endpoint::Token => self.token_endpoint.as_deref(),
+            Endpoint::Authorization => self.authorization_endpoint.as_deref(),
+            Endpoint::Userinfo => self.userinfo_endpoint.as_deref(),
+            Endpoint::Jwks => self.jwks_uri.as_deref(),
+            Endpoint::Introspection => self.introspection_endpoint.as_deref(),
+            Endpoint::Revocation => self.revocation_endpoint.as_deref(),
+            Endpoint::EndSession => self.end_session_endpoint.as_deref(),
+            Endpoint::DeviceAuthorization => self.device_authorization_endpoint.as_deref(),
+        }
+    }
 }
 
 impl Default for IdentityServerConfigs {
     fn default() -> Self {
         Self {
+            provider: IdentityProvider::default(),
+            provider_error: None,
             url: Default::default(),
             realm: Default::default(),
             audience: Default::default(),
             issuer: Default::default(),
             client_id: Default::default(),
             client_secret: Default::default(),
-            grant_type: "client_credentials".to_owned(),
+            token_endpoint_auth_method: TokenEndpointAuthMethod::default(),
+            token_endpoint_auth_method_error: None,
+            grant_type: GrantType::default(),
+            grant_type_error: None,
+            scopes: Vec::new(),
+            resources: Vec::new(),
+            redirect_uri: Default::default(),
+            pkce: true,
+            username: Default::default(),
+            password: Default::default(),
+            refresh_token: Default::default(),
+            subject_token: Default::default(),
+            subject_token_type: ACCESS_TOKEN_TYPE.to_owned(),
+            assertion: Default::default(),
+            discovery: None,
+            discovery_error: None,
+        }
+    }
+}
+
+/// # IdentityProvider
+///
+/// Enum representing the identity providers with a known endpoint layout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum IdentityProvider {
+    /// Keycloak: endpoints under `{url}/realms/{realm}/protocol/openid-connect` (default)
+    #[default]
+    Keycloak,
+    /// Auth0: endpoints under the tenant domain
+    Auth0,
+    /// Amazon Cognito: issuer from the user pool ID, endpoints under the user pool domain
+    Cognito,
+    /// Any OpenID Connect provider, endpoints come from the issuer's discovery document
+    Generic,
+}
+
+impl IdentityProvider {
+    /// Parses a provider name.
+    ///
+    /// Accepts "keycloak", "auth0", "cognito" and "generic" (or "oidc"), in
+    /// lowercase or uppercase.
+    ///
+    /// ## Parameters
+    ///
+    /// * `value` - A string slice containing the provider name
+    ///
+    /// ## Returns
+    ///
+    /// The `IdentityProvider`, or `None` for an unknown name.
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "keycloak" => Some(Self::Keycloak),
+            "auth0" => Some(Self::Auth0),
+            "cognito" => Some(Self::Cognito),
+            "generic" | "oidc" => Some(Self::Generic),
+            _ => None,
+        }
+    }
+}
+
+impl From<&str> for IdentityProvider {
+    /// Creates an `IdentityProvider` from a string slice.
+    ///
+    /// Accepts the names of `IdentityProvider::parse`. Any other value returns
+    /// `IdentityProvider::Keycloak`.
+    ///
+    /// ## Parameters
+    ///
+    /// * `value` - A string slice containing the provider name
+    ///
+    /// ## Returns
+    ///
+    /// An `IdentityProvider` variant corresponding to the input string
+    fn from(value: &str) -> Self {
+        Self::parse(value).unwrap_or_default()
+    }
+}
+
+impl Display for IdentityProvider {
+    /// Formats the `IdentityProvider`.
+    ///
+    /// ## Returns
+    ///
+    /// A string representation of the provider (e.g., "Keycloak").
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::Keycloak => write!(f, "Keycloak"),
+            Self::Auth0 => write!(f, "Auth0"),
+            Self::Cognito => write!(f, "Cognito"),
+            Self::Generic => write!(f, "Generic"),
+        }
+    }
+}
+
+/// # GrantType
+///
+/// Enum representing the OAuth 2.0 grant types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GrantType {
+    /// Client credentials: the client acts on its own behalf (default)
+    #[default]
+    ClientCredentials,
+    /// Authorization code, with PKCE unless disabled
+    AuthorizationCode,
+    /// Resource owner password credentials
+    Password,
+    /// Refresh token
+    RefreshToken,
+    /// Device authorization (RFC 8628)
+    DeviceCode,
+    /// Token exchange (RFC 8693)
+    TokenExchange,
+    /// JWT bearer assertion (RFC 7523)
+    JwtBearer,
+}
+
+impl GrantType {
+    /// Returns the `grant_type` value sent to the token endpoint.
+    ///
+    /// ## Returns
+    ///
+    /// The grant type name, or its URN for the extension grants
+    /// (e.g., "urn:ietf:params:oauth:grant-type:device_code").
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::ClientCredentials => "client_credentials",
+            Self::AuthorizationCode => "authorization_code",
+            Self::Password => "password",
+            Self::RefreshToken => "refresh_token",
+            Self::DeviceCode => "urn:ietf:params:oauth:grant-type:device_code",
+            Self::TokenExchange => "urn:ietf:params:oauth:grant-type:token-exchange",
+            Self::JwtBearer => "urn:ietf:params:oauth:grant-type:jwt-bearer",
+        }
+    }
+}
+
+impl GrantType {
+    /// Parses a grant type.
+    ///
+    /// Accepts the grant type names, the URNs of the extension grants and their
+    /// short forms ("device_code", "token_exchange", "jwt_bearer"), in lowercase
+    /// or uppercase, with `-` or `_`. Unsupported grants such as "implicit" are
+    /// rejected.
+    ///
+    /// ## Parameters
+    ///
+    /// * `value` - A string slice containing the grant type
+    ///
+    /// ## Returns
+    ///
+    /// The `GrantType`, or `None` for an unknown or unsupported grant type.
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim().to_lowercase().replace('-', "_");
+        let value = value
+            .strip_prefix("urn:ietf:params:oauth:grant_type:")
+            .unwrap_or(&value);
+
+        match value {
+            "client_credentials" => Some(Self::ClientCredentials),
+            "authorization_code" => Some(Self::AuthorizationCode),
+            "password" => Some(Self::Password),
+            "refresh_token" => Some(Self::RefreshToken),
+            "device_code" => Some(Self::DeviceCode),
+            "token_exchange" => Some(Self::TokenExchange),
+            "jwt_bearer" => Some(Self::JwtBearer),
+            _ => None,
+        }
+    }
+}
+
+impl From<&str> for GrantType {
+    /// Creates a `GrantType` from a string slice.
+    ///
+    /// Accepts the values of `GrantType::parse`. Any other value returns
+    /// `GrantType::ClientCredentials`.
+    ///
+    /// ## Parameters
+    ///
+    /// * `value` - A string slice containing the grant type
+    ///
+    /// ## Returns
+    ///
+    /// A `GrantType` variant corresponding to the input string
+    fn from(value: &str) -> Self {
+        Self::parse(value).unwrap_or_default()
+    }
+}
+
+impl Display for GrantType {
+    /// Formats the `GrantType`.
+    ///
+    /// ## Returns
+    ///
+    /// The `grant_type` value (e.g., "client_credentials").
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// # TokenEndpointAuthMethod
+///
+/// Enum representing how the client authenticates to the token endpoint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TokenEndpointAuthMethod {
+    /// Client secret in the HTTP Basic authorization header (default)
+    #[default]
+    ClientSecretBasic,
+    /// Client secret in the request body
+    ClientSecretPost,
+    /// JWT assertion signed with the client private key (RFC 7523)
+    PrivateKeyJwt,
+    /// Public client without authentication, "none"
+    Public,
+}
+
+impl TokenEndpointAuthMethod {
+    /// Parses an authentication method.
+    ///
+    /// Accepts the discovery metadata names ("client_secret_basic",
+    /// "client_secret_post", "private_key_jwt" and "none"), in lowercase or
+    /// uppercase, with `-` or `_`.
+    ///
+    /// ## Parameters
+    ///
+    /// * `value` - A string slice containing the authentication method
+    ///
+    /// ## Returns
+    ///
+    /// The `TokenEndpointAuthMethod`, or `None` for an unknown method.
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().replace('-', "_").as_str() {
+            "client_secret_basic" => Some(Self::ClientSecretBasic),
+            "client_secret_post" => Some(Self::ClientSecretPost),
+            "private_key_jwt" => Some(Self::PrivateKeyJwt),
+            "none" => Some(Self::Public),
+            _ => None,
+        }
+    }
+
+    /// Returns the method name used in the discovery metadata.
+    ///
+    /// ## Returns
+    ///
+    /// The method name (e.g., "client_secret_basic").
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::ClientSecretBasic => "client_secret_basic",
+            Self::ClientSecretPost => "client_secret_post",
+            Self::PrivateKeyJwt => "private_key_jwt",
+            Self::Public => "none",
         }
     }
+
+    /// Checks if the method authenticates with the client secret.
+    fn uses_secret(&self) -> bool {
+        matches!(self, Self::ClientSecretBasic | Self::ClientSecretPost)
+    }
+}
+
+impl Display for TokenEndpointAuthMethod {
+    /// Formats the `TokenEndpointAuthMethod`.
+    ///
+    /// ## Returns
+    ///
+    /// The method name (e.g., "private_key_jwt").
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn configs(provider: IdentityProvider, url: &str, realm: &str) -> IdentityServerConfigs {
+        IdentityServerConfigs {
+            provider,
+            url: url.to_owned(),
+            realm: realm.to_owned(),
+            client_id: "api".to_owned(),
+            client_secret: "secret".to_owned(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn should_derive_keycloak_endpoints() {
+        let cfgs = configs(
+            IdentityProvider::Keycloak,
+            "https://sso.example.com/",
+            "app",
+        );
+
+        assert_eq!(
+            cfgs.derived_issuer().unwrap(),
+            "https://sso.example.com/realms/app"
+        );
+        assert_eq!(
+            cfgs.discovery_endpoint().unwrap(),
+            "https://sso.example.com/realms/app/.well-known/openid-configuration"
+        );
+        assert_eq!(
+            cfgs.token_endpoint().unwrap(),
+            "https://sso.example.com/realms/app/protocol/openid-connect/token"
+        );
+        assert_eq!(
+            cfgs.introspection_endpoint().unwrap(),
+            "https://sso.example.com/realms/app/protocol/openid-connect/token/introspect"
+        );
+        assert!(
+            configs(IdentityProvider::Keycloak, "", "app")
+                .token_endpoint()
+                .is_none()
+        );
+        assert_eq!(
+            configs(
+                IdentityProvider::Keycloak,
+                "https://sso.example.com",
+                "my realm/x"
+            )
+            .derived_issuer()
+            .unwrap(),
+            "https://sso.example.com/realms/my%20realm%2Fx"
+        );
+    }
+
+    #[test]
+    fn should_derive_auth0_and_cognito_endpoints() {
+        let cfgs = configs(IdentityProvider::Auth0, "", "tenant.eu.auth0.com");
+        assert_eq!(
+            cfgs.derived_issuer().unwrap(),
+            "https://tenant.eu.auth0.com/"
+        );
+        assert_eq!(
+            cfgs.jwks_uri().unwrap(),
+            "https://tenant.eu.auth0.com/.well-known/jwks.json"
+        );
+        assert!(cfgs.introspection_endpoint().is_none());
+
+        let cfgs = configs(
+            IdentityProvider::Cognito,
+            "https://app.auth.eu-west-1.amazoncognito.com",
+            "eu-west-1_AbCdEf",
+        );
+        assert_eq!(
+            cfgs.jwks_uri().unwrap(),
+            "https://cognito-idp.eu-west-1.amazonaws.com/eu-west-1_AbCdEf/.well-known/jwks.json"
+        );
+        assert_eq!(
+            cfgs.token_endpoint().unwrap(),
+            "https://app.auth.eu-west-1.amazoncognito.com/oauth2/token"
+        );
+
+        let cfgs = configs(IdentityProvider::Generic, "https://idp.example.com", "");
+        assert_eq!(
+            cfgs.discovery_endpoint().unwrap(),
+            "https://idp.example.com/.well-known/openid-configuration"
+        );
+        assert!(cfgs.token_endpoint().is_none());
+    }
+
+    #[test]
+    fn should_validate_issuer() {
+        let mut cfgs = configs(IdentityProvider::Keycloak, "https://sso.example.com", "app");
+        assert_eq!(cfgs.validate(), Ok(()));
+
+        cfgs.issuer = "https://sso.example.com/realms/app".to_owned();
+        assert_eq!(cfgs.validate(), Ok(()));
+
+        cfgs.issuer = "https://sso.example.com/realms/other".to_owned();
+        assert!(cfgs.validate().is_err());
+
+        assert!(
+            configs(IdentityProvider::Keycloak, "https://sso.example.com", "")
+                .validate()
+                .is_err()
+        );
+        assert!(
+            configs(IdentityProvider::Cognito, "", "AbCdEf")
+                .validate()
+                .is_err()
+        );
+        assert!(
+            configs(IdentityProvider::Generic, "sso.example.com", "")
+                .validate()
+                .is_err()
+        );
+        assert_eq!(
+            IdentityProvider::parse("OIDC"),
+            Some(IdentityProvider::Generic)
+        );
+        assert_eq!(IdentityProvider::parse("keyclaok"), None);
+        cfgs.issuer = String::new();
+        cfgs.provider_error = Some(ConfigsError::Invalid("keyclaok".into()));
+        assert!(matches!(cfgs.validate(), Err(ConfigsError::Invalid(_))));
+    }
+
+    const DISCOVERY: &str = r#"{
+        "issuer": "https://idp.example.com",
+        "token_endpoint": "https://idp.example.com/connect/token",
+        "jwks_uri": "https://idp.example.com/keys",
+        "scopes_supported": ["openid", "profile"],
+        "grant_types_supported": ["authorization_code", "client_credentials"],
+        "id_token_signing_alg_values_supported": ["RS256", "ES256"],
+        "claims_supported": ["sub"]
+    }"#;
+
+    #[test]
+    fn should_load_discovery_document() {
+        let discovery = IdentityServerDiscovery::from_json(DISCOVERY).unwrap();
+        assert_eq!(
+            discovery.id_token_signing_alg_values_supported,
+            ["RS256", "ES256"]
+        );
+        assert!(discovery.supports_grant_type("client_credentials"));
+
+        let mut cfgs = configs(IdentityProvider::Generic, "https://idp.example.com", "");
+        cfgs.discovery = Some(discovery);
+        assert_eq!(cfgs.validate(), Ok(()));
+        assert_eq!(
+            cfgs.token_endpoint().unwrap(),
+            "https://idp.example.com/connect/token"
+        );
+        assert!(cfgs.userinfo_endpoint().is_none());
+
+        let defaults =
+            IdentityServerDiscovery::from_json(r#"{"issuer": "https://idp.example.com"}"#).unwrap();
+        assert_eq!(
+            defaults.grant_types_supported,
+            ["authorization_code", "implicit"]
+        );
+
+        assert!(matches!(
+            IdentityServerDiscovery::from_json("{}"),
+            Err(ConfigsError::Parse(_))
+        ));
+
+        cfgs.discovery_error = IdentityServerDiscovery::from_file("/missing/discovery.json").err();
+        assert!(matches!(
+            cfgs.validate(),
+            Err(ConfigsError::FileNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn should_cross_check_discovery_document() {
+        let mut cfgs = configs(IdentityProvider::Keycloak, "https://sso.example.com", "app");
+        cfgs.discovery = Some(IdentityServerDiscovery::from_json(DISCOVERY).unwrap());
+        assert!(matches!(cfgs.validate(), Err(ConfigsError::Invalid(_))));
+
+        let mut cfgs = configs(IdentityProvider::Generic, "https://idp.example.com", "");
+        cfgs.grant_type = GrantType::Password;
+        cfgs.username = "user".to_owned();
+        cfgs.password = "pass".to_owned();
+        cfgs.discovery = Some(IdentityServerDiscovery::from_json(DISCOVERY).unwrap());
+        assert!(matches!(cfgs.validate(), Err(ConfigsError::Invalid(_))));
+    }
+
+    #[test]
+    fn should_parse_grant_type() {
+        assert_eq!(
+            GrantType::from("Authorization_Code"),
+            GrantType::AuthorizationCode
+        );
+        assert_eq!(GrantType::from("token-exchange"), GrantType::TokenExchange);
+        assert_eq!(
+            GrantType::from("urn:ietf:params:oauth:grant-type:jwt-bearer"),
+            GrantType::JwtBearer
+        );
+        assert_eq!(GrantType::from("unknown"), GrantType::ClientCredentials);
+        assert_eq!(GrantType::parse("implicit"), None);
+        assert_eq!(
+            GrantType::parse("client-credentials"),
+            Some(GrantType::ClientCredentials)
+        );
+        assert_eq!(
+            TokenEndpointAuthMethod::parse("Private-Key-JWT"),
+            Some(TokenEndpointAuthMethod::PrivateKeyJwt)
+        );
+        assert_eq!(TokenEndpointAuthMethod::parse("tls_client_auth"), None);
+        assert_eq!(
+            GrantType::DeviceCode.to_string(),
+            "urn:ietf:params:oauth:grant-type:device_code"
+        );
+    }
+
+    #[test]
+    fn should_validate_grant_fields() {
+        let mut cfgs = configs(IdentityProvider::Keycloak, "https://sso.example.com", "app");
+        cfgs.client_secret = String::new();
+        assert!(matches!(cfgs.validate(), Err(ConfigsError::Missing(_))));
+
+        cfgs.grant_type = GrantType::AuthorizationCode;
+        cfgs.redirect_uri = "https://app.example.com/callback".to_owned();
+        assert_eq!(cfgs.validate(), Ok(()));
+
+        cfgs.pkce = false;
+        assert!(matches!(cfgs.validate(), Err(ConfigsError::Invalid(_))));
+
+        cfgs.grant_type = GrantType::DeviceCode;
+        assert_eq!(cfgs.validate(), Ok(()));
+
+        cfgs.provider = IdentityProvider::Cognito;
+        cfgs.realm = "eu-west-1_AbCdEf".to_owned();
+        assert!(matches!(cfgs.validate(), Err(ConfigsError::Invalid(_))));
+
+        cfgs.grant_type = GrantType::TokenExchange;
+        assert!(matches!(cfgs.validate(), Err(ConfigsError::Missing(_))));
+
+        let mut cfgs = configs(IdentityProvider::Keycloak, "https://sso.example.com", "app");
+        cfgs.client_secret = String::new();
+        cfgs.token_endpoint_auth_method = TokenEndpointAuthMethod::PrivateKeyJwt;
+        assert_eq!(cfgs.validate(), Ok(()));
+
+        cfgs.token_endpoint_auth_method = TokenEndpointAuthMethod::Public;
+        assert!(matches!(cfgs.validate(), Err(ConfigsError::Invalid(_))));
+
+        cfgs.token_endpoint_auth_method = TokenEndpointAuthMethod::ClientSecretPost;
+        cfgs.client_secret = "secret".to_owned();
+        cfgs.grant_type_error = Some(ConfigsError::Invalid("implicit".into()));
+        assert!(matches!(cfgs.validate(), Err(ConfigsError::Invalid(_))));
+
+        cfgs.grant_type_error = None;
+        cfgs.token_endpoint_auth_method_error = Some(ConfigsError::Invalid("mtls".into()));
+        assert!(matches!(cfgs.validate(), Err(ConfigsError::Invalid(_))));
+    }
+
+    #[test]
+    fn should_render_token_parameters() {
+        let mut cfgs = configs(IdentityProvider::Auth0, "", "tenant.auth0.com");
+        cfgs.grant_type = GrantType::Password;
+        cfgs.username = "user".to_owned();
+        cfgs.password = "pass".to_owned();
+        cfgs.scopes = split_list("openid, profile offline_access", |c| {
+            c == ',' || c.is_whitespace()
+        });
+        cfgs.audience = "https://api.example.com".to_owned();
+
+        assert_eq!(
+            cfgs.token_parameters(),
+            vec![
+                ("grant_type", "password".to_owned()),
+                ("username", "user".to_owned()),
+                ("password", "pass".to_owned()),
+                ("scope", "openid profile offline_access".to_owned()),
+                ("audience", "https://api.example.com".to_owned()),
+            ]
+        );
+    }
 }
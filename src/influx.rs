@@ -7,26 +7,47 @@
 //! This module provides configuration options for connecting to and
 //! working with InfluxDB time-series database.
 
+use std::{
+    collections::BTreeMap,
+    fmt::{Display, Formatter, Result as FmtResult},
+    path::Path,
+    time::Duration,
+};
+
+use crate::{errors::ConfigsError, uri};
+
 /// # InfluxConfigs
 ///
 /// Configuration structure for InfluxDB connections.
 ///
 /// This structure defines the connection parameters and settings for working
-/// with InfluxDB, including server address, port, bucket, and authentication token.
+/// with InfluxDB, including server address, port, and the fields of each API version:
+///
+/// - `InfluxVersion::V1`: `database`, optional `retention_policy`, `username` and `password`
+/// - `InfluxVersion::V2`: `org`, `bucket` and `token`
+/// - `InfluxVersion::V3`: `database` and `token`
 ///
 /// ## Examples
 ///
 /// ```
-/// use configs::InfluxConfigs;
+/// use configs::influx::InfluxConfigs;
 ///
 /// let influx_config = InfluxConfigs::default();
-/// println!("InfluxDB server address: {}", influx_config.addr());
+/// assert_eq!(influx_config.url(), "http://localhost:8086");
 /// ```
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone)]
 pub struct InfluxConfigs {
+    /// ENV KEY: "INFLUX_VERSION"
+    ///
+    /// The InfluxDB API version (Default: InfluxVersion::V2)
+    pub version: InfluxVersion,
+    /// Error raised when "INFLUX_VERSION" is not a known version, reported by
+    /// `validate()` (Default: None)
+    pub version_error: Option<ConfigsError>,
     /// ENV KEY: "INFLUX_HOST"
     ///
-    /// The InfluxDB server host URL (Default: "http://localhost")
+    /// The InfluxDB server host; a URL such as "https://influx:8086" also sets
+    /// the TLS mode and, when it has one, the port (Default: "localhost")
     pub host: String,
     /// ENV KEY: "INFLUX_PORT"
     ///
@@ -38,36 +59,687 @@ pub struct InfluxConfigs {
     pub bucket: String,
     /// ENV KEY: "INFLUX_TOKEN"
     ///
-    /// The authentication token for InfluxDB (Default: "token")
+    /// The authentication token for InfluxDB (Default: "")
     pub token: String,
+    /// ENV KEY: "INFLUX_ORG"
+    ///
+    /// The organization owning the bucket, v2 only (Default: "")
+    pub org: String,
+    /// ENV KEY: "INFLUX_DATABASE"
+    ///
+    /// The database, v1 and v3 only (Default: "")
+    pub database: String,
+    /// ENV KEY: "INFLUX_RETENTION_POLICY"
+    ///
+    /// The retention policy, v1 only, `None` uses the database default (Default: None)
+    pub retention_policy: Option<String>,
+    /// ENV KEY: "INFLUX_USERNAME"
+    ///
+    /// The username, v1 only (Default: "")
+    pub username: String,
+    /// ENV KEY: "INFLUX_PASSWORD"
+    ///
+    /// The password, v1 only (Default: "")
+    pub password: String,
+    /// TLS (https) settings
+    pub tls: InfluxTlsConfigs,
+    /// Write settings
+    pub write: InfluxWriteConfigs,
+    /// Error raised when "INFLUX_URL" or a URL in "INFLUX_HOST" is not supported,
+    /// reported by `validate()` (Default: None)
+    pub url_error: Option<ConfigsError>,
+}
+
+/// # InfluxTlsConfigs
+///
+/// TLS settings of the InfluxDB connection.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InfluxTlsConfigs {
+    /// ENV KEY: "INFLUX_TLS_ENABLED"
+    ///
+    /// Whether the connection uses https (Default: false)
+    pub enabled: bool,
+    /// ENV KEY: "INFLUX_TLS_CA_PATH"
+    ///
+    /// Path to the CA certificate used to verify the server (Default: "")
+    pub ca_path: String,
+    /// ENV KEY: "INFLUX_TLS_SKIP_VERIFY"
+    ///
+    /// Whether to skip the server certificate verification (Default: false)
+    pub skip_verify: bool,
+}
+
+/// # InfluxWriteConfigs
+///
+/// Settings of the points written to InfluxDB.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InfluxWriteConfigs {
+    /// ENV KEY: "INFLUX_PRECISION"
+    ///
+    /// The timestamp precision (Default: InfluxPrecision::Nanoseconds)
+    pub precision: InfluxPrecision,
+    /// Error raised when "INFLUX_PRECISION" is not a known precision, reported
+    /// by `validate()` (Default: None)
+    pub precision_error: Option<ConfigsError>,
+    /// ENV KEY: "INFLUX_BATCH_SIZE"
+    ///
+    /// The maximum number of points per write (Default: 5000)
+    pub batch_size: usize,
+    /// ENV KEY: "INFLUX_FLUSH_INTERVAL"
+    ///
+    /// The maximum time points are buffered, in milliseconds (Default: 1000)
+    pub flush_interval: Duration,
+    /// ENV KEY: "INFLUX_GZIP"
+    ///
+    /// Whether write requests are gzip-compressed (Default: false)
+    pub gzip: bool,
+    /// ENV KEY: "INFLUX_DEFAULT_TAGS"
+    ///
+    /// Tags added to every point, as "key=value" pairs separated by commas (Default: empty)
+    pub default_tags: BTreeMap<String, String>,
 }
 
 pub const INFLUX_HOST_ENV_KEY: &str = "INFLUX_HOST";
 pub const INFLUX_PORT_ENV_KEY: &str = "INFLUX_PORT";
 pub const INFLUX_BUCKET_ENV_KEY: &str = "INFLUX_BUCKET";
 pub const INFLUX_TOKEN_ENV_KEY: &str = "INFLUX_TOKEN";
+pub const INFLUX_VERSION_ENV_KEY: &str = "INFLUX_VERSION";
+pub const INFLUX_ORG_ENV_KEY: &str = "INFLUX_ORG";
+pub const INFLUX_DATABASE_ENV_KEY: &str = "INFLUX_DATABASE";
+pub const INFLUX_RETENTION_POLICY_ENV_KEY: &str = "INFLUX_RETENTION_POLICY";
+pub const INFLUX_USERNAME_ENV_KEY: &str = "INFLUX_USERNAME";
+pub const INFLUX_PASSWORD_ENV_KEY: &str = "INFLUX_PASSWORD";
+pub const INFLUX_URL_ENV_KEY: &str = "INFLUX_URL";
+pub const INFLUX_TLS_ENABLED_ENV_KEY: &str = "INFLUX_TLS_ENABLED";
+pub const INFLUX_TLS_CA_PATH_ENV_KEY: &str = "INFLUX_TLS_CA_PATH";
+pub const INFLUX_TLS_SKIP_VERIFY_ENV_KEY: &str = "INFLUX_TLS_SKIP_VERIFY";
+pub const INFLUX_PRECISION_ENV_KEY: &str = "INFLUX_PRECISION";
+pub const INFLUX_BATCH_SIZE_ENV_KEY: &str = "INFLUX_BATCH_SIZE";
+pub const INFLUX_FLUSH_INTERVAL_ENV_KEY: &str = "INFLUX_FLUSH_INTERVAL";
+pub const INFLUX_GZIP_ENV_KEY: &str = "INFLUX_GZIP";
+pub const INFLUX_DEFAULT_TAGS_ENV_KEY: &str = "INFLUX_DEFAULT_TAGS";
+
+const INFLUX_DEFAULT_PORT: u64 = 8086;
 
 impl InfluxConfigs {
+    /// Creates a new `InfluxConfigs` instance from environments variables.
+    ///
+    /// "INFLUX_URL" provides the base scheme, host and port, then the individual
+    /// keys override them. An unsupported URL is kept in `url_error`, and an unknown
+    /// version in `version_error`.
+    ///
+    /// ## Returns
+    ///
+    /// A new `InfluxConfigs` from environments variables.
     pub fn new() -> Self {
         let mut cfgs = Self::default();
 
-        cfgs.host = std::env::var(INFLUX_HOST_ENV_KEY).unwrap_or(cfgs.host);
+        if let Ok(url) = std::env::var(INFLUX_URL_ENV_KEY) {
+            cfgs = cfgs.apply_url(&url, None);
+        }
+        if let Ok(host) = std::env::var(INFLUX_HOST_ENV_KEY) {
+            cfgs = cfgs.apply_host(host);
+        }
         cfgs.port = std::env::var(INFLUX_PORT_ENV_KEY)
             .ok()
             .and_then(|v| v.parse::<u64>().ok())
             .unwrap_or(cfgs.port);
         cfgs.bucket = std::env::var(INFLUX_BUCKET_ENV_KEY).unwrap_or(cfgs.bucket);
         cfgs.token = std::env::var(INFLUX_TOKEN_ENV_KEY).unwrap_or(cfgs.token);
+        if let Ok(value) = std::env::var(INFLUX_VERSION_ENV_KEY) {
+            match InfluxVersion::parse(&value) {
+                Some(version) => cfgs.version = version,
+                None => {
+                    cfgs.version_error = Some(ConfigsError::Invalid(format!(
+                        "{} \"{}\" is not a known version",
+                        INFLUX_VERSION_ENV_KEY, value
+                    )))
+                }
+            }
+        }
+        cfgs.org = std::env::var(INFLUX_ORG_ENV_KEY).unwrap_or(cfgs.org);
+        cfgs.database = std::env::var(INFLUX_DATABASE_ENV_KEY).unwrap_or(cfgs.database);
+        cfgs.retention_policy = std::env::var(INFLUX_RETENTION_POLICY_ENV_KEY)
+            .ok()
+            .filter(|v| !v.is_empty())
+            .or(cfgs.retention_policy);
+        cfgs.username = std::env::var(INFLUX_USERNAME_ENV_KEY).unwrap_or(cfgs.username);
+        cfgs.password = std::env::var(INFLUX_PASSWORD_ENV_KEY).unwrap_or(cfgs.password);
+        cfgs.tls.enabled = std::env::var(INFLUX_TLS_ENABLED_ENV_KEY)
+            .ok()
+            .and_then(|v| v.parse::<bool>().ok())
+            .unwrap_or(cfgs.tls.enabled);
+        cfgs.tls.ca_path = std::env::var(INFLUX_TLS_CA_PATH_ENV_KEY).unwrap_or(cfgs.tls.ca_path);
+        cfgs.tls.skip_verify = std::env::var(INFLUX_TLS_SKIP_VERIFY_ENV_KEY)
+            .ok()
+            .and_then(|v| v.parse::<bool>().ok())
+            .unwrap_or(cfgs.tls.skip_verify);
+        if let Ok(value) = std::env::var(INFLUX_PRECISION_ENV_KEY) {
+            match InfluxPrecision::parse(&value) {
+                Some(precision) => cfgs.write.precision = precision,
+                None => {
+                    cfgs.write.precision_error = Some(ConfigsError::Invalid(format!(
+                        "{} \"{}\" is not a known precision",
+                        INFLUX_PRECISION_ENV_KEY, value
+                    )))
+                }
+            }
+        }
+        cfgs.write.batch_size = std::env::var(INFLUX_BATCH_SIZE_ENV_KEY)
+            .ok()
+            .and_then(|v| v.parse::<usize>().ok())
+            .unwrap_or(cfgs.write.batch_size);
+        cfgs.write.flush_interval = std::env::var(INFLUX_FLUSH_INTERVAL_ENV_KEY)
+            .ok()
+            .and_then(|v| v.parse::<u64>().ok())
+            .map(Duration::from_millis)
+            .unwrap_or(cfgs.write.flush_interval);
+        cfgs.write.gzip = std::env::var(INFLUX_GZIP_ENV_KEY)
+            .ok()
+            .and_then(|v| v.parse::<bool>().ok())
+            .unwrap_or(cfgs.write.gzip);
+        cfgs.write.default_tags = std::env::var(INFLUX_DEFAULT_TAGS_ENV_KEY)
+            .map(|v| parse_tags(&v))
+            .unwrap_or(cfgs.write.default_tags);
 
         cfgs
     }
 
+    /// Applies the scheme, host and port of a URL such as "https://influx:8086".
+    ///
+    /// The `https` scheme enables TLS. Without a port, the scheme default is used
+    /// (443 for `https`, 80 for `http`). The URL cannot have a path, since the
+    /// client builds the API paths itself.
+    ///
+    /// ## Parameters
+    ///
+    /// * `url` - The InfluxDB server URL
+    ///
+    /// ## Returns
+    ///
+    /// The updated `InfluxConfigs`, or a `ConfigsError::Invalid` for an unsupported URL.
+    pub fn with_url(self, url: &str) -> Result<Self, ConfigsError> {
+        self.with_url_or_port(url, None)
+    }
+
+    /// Applies a URL, using `default_port` rather than the scheme default when it has no port.
+    fn with_url_or_port(
+        mut self,
+        url: &str,
+        default_port: Option<u64>,
+    ) -> Result<Self, ConfigsError> {
+        uri::validate_http_url(INFLUX_URL_ENV_KEY, url)?;
+        let parsed = uri::parse(url)?;
+        if parsed.path.as_deref().is_some_and(|path| !path.is_empty()) {
+            return Err(ConfigsError::Invalid(format!(
+                "{} must not have a path",
+                INFLUX_URL_ENV_KEY
+            )));
+        }
+        let (host, port) = uri::split_host_port(&parsed.hosts)?;
+
+        self.tls.enabled = parsed.scheme == "https";
+        self.host = host;
+        self.port = match (port, default_port) {
+            (Some(port), _) => u64::from(port),
+            (None, Some(port)) => port,
+            (None, None) if self.tls.enabled => 443,
+            (None, None) => 80,
+        };
+
+        Ok(self)
+    }
+
+    /// Applies "INFLUX_HOST", either a host name or a URL.
+    ///
+    /// A URL without a port keeps the current port rather than the scheme default,
+    /// so "http://influx" still connects to 8086.
+    fn apply_host(mut self, host: String) -> Self {
+        if !host.contains("://") {
+            self.host = host;
+            return self;
+        }
+
+        let port = self.port;
+        self.apply_url(&host, Some(port))
+    }
+
+    /// Applies a URL, keeping the configuration and the error when it is not supported.
+    fn apply_url(self, url: &str, default_port: Option<u64>) -> Self {
+        match self.clone().with_url_or_port(url, default_port) {
+            Ok(cfgs) => cfgs,
+            Err(e) => Self {
+                url_error: Some(e),
+                ..self
+            },
+        }
+    }
+
+    /// Returns the server URL, e.g. "http://localhost:8086".
+    ///
+    /// ## Returns
+    ///
+    /// The URL with an `https` scheme when TLS is enabled, `http` otherwise.
+    pub fn url(&self) -> String {
+        let scheme = if self.tls.enabled { "https" } else { "http" };
+
+        format!(
+            "{}://{}:{}",
+            scheme,
+            uri::format_host(&self.host),
+            self.port
+        )
+    }
+
+    /// Validates that the fields of the selected API version are set.
+    ///
+    /// The URL and version must be supported. v1 requires `database`, with
+    /// `username` and `password` set together. v2 requires `org`, `bucket` and
+    /// `token`. v3 requires `database` and `token`.
+    /// The host must be set, the CA certificate must exist when TLS is enabled,
+    /// the precision must be known, the write batch size and flush interval must
+    /// be positive, and default tags need a key.
+    ///
+    /// ## Returns
+    ///
+    /// `Ok(())` if the configuration is consistent, a `ConfigsError` otherwise.
+    pub fn validate(&self) -> Result<(), ConfigsError> {
+        if let Some(e) = self.url_error.as_ref().or(self.version_error.as_ref()) {
+            return Err(e.clone());
+        }
+
+        let required: &[(&str, &str)] = match self.version {
+            InfluxVersion::V1 => &[(INFLUX_DATABASE_ENV_KEY, &self.database)],
+            InfluxVersion::V2 => &[
+                (INFLUX_ORG_ENV_KEY, &self.org),
+                (INFLUX_BUCKET_ENV_KEY, &self.bucket),
+                (INFLUX_TOKEN_ENV_KEY, &self.token),
+            ],
+            InfluxVersion::V3 => &[
+                (INFLUX_DATABASE_ENV_KEY, &self.database),
+                (INFLUX_TOKEN_ENV_KEY, &self.token),
+            ],
+        };
+        if let Some((key, _)) = required.iter().find(|(_, value)| value.is_empty()) {
+            return Err(ConfigsError::Missing(format!(
+                "{} (InfluxDB {})",
+                key, self.version
+            )));
+        }
+
+        if self.version == InfluxVersion::V1 && self.username.is_empty() != self.password.is_empty()
+        {
+            return Err(ConfigsError::Invalid(format!(
+                "{} and {} must be set together",
+                INFLUX_USERNAME_ENV_KEY, INFLUX_PASSWORD_ENV_KEY
+            )));
+        }
+
+        if self.host.is_empty() {
+            return Err(ConfigsError::Missing(INFLUX_HOST_ENV_KEY.into()));
+        }
+        if self.tls.enabled
+            && !self.tls.ca_path.is_empty()
+            && !Path::new(&self.tls.ca_path).is_file()
+        {
+            return Err(ConfigsError::FileNotFound(self.tls.ca_path.clone()));
+        }
+        if let Some(e) = &self.write.precision_error {
+            return Err(e.clone());
+        }
+        if self.write.batch_size == 0 || self.write.flush_interval.is_zero() {
+            return Err(ConfigsError::Invalid(format!(
+                "{} and {} must be positive",
+                INFLUX_BATCH_SIZE_ENV_KEY, INFLUX_FLUSH_INTERVAL_ENV_KEY
+            )));
+        }
+        if self.write.default_tags.keys().any(|key| key.is_empty()) {
+            return Err(ConfigsError::Invalid(format!(
+                "{} contains a tag without a key",
+                INFLUX_DEFAULT_TAGS_ENV_KEY
+            )));
+        }
+
+        Ok(())
+    }
+
     /// Returns the formatted server address (host:port).
     ///
     /// ## Returns
     ///
-    /// A String containing the formatted address (e.g., "http://localhost:8086").
+    /// A String containing the formatted address (e.g., "localhost:8086").
     pub fn addr(&self) -> String {
-        format!("{}:{}", self.host, self.port)
+        format!("{}:{}", uri::format_host(&self.host), self.port)
+    }
+}
+
+/// Parses "key=value" pairs separated by commas, ignoring empty entries.
+fn parse_tags(value: &str) -> BTreeMap<String, String> {
+    value
+        .split(',')
+        .filter(|pair| !pair.trim().is_empty())
+        .map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (key.trim().to_owned(), value.trim().to_owned())
+        })
+        .collect()
+}
+
+impl Default for InfluxConfigs {
+    fn default() -> Self {
+        Self {
+            version: Default::default(),
+            version_error: None,
+            host: "localhost".to_owned(),
+            port: INFLUX_DEFAULT_PORT,
+            bucket: "default".to_owned(),
+            token: Default::default(),
+            org: Default::default(),
+            database: Default::default(),
+            retention_policy: None,
+            username: Default::default(),
+            password: Default::default(),
+            tls: Default::default(),
+            write: Default::default(),
+            url_error: None,
+        }
+    }
+}
+
+impl Default for InfluxWriteConfigs {
+    fn default() -> Self {
+        Self {
+            precision: Default::default(),
+            precision_error: None,
+            batch_size: 5000,
+            flush_interval: Duration::from_millis(1000),
+            gzip: false,
+            default_tags: Default::default(),
+        }
+    }
+}
+
+/// # InfluxPrecision
+///
+/// Enum representing the precision of point timestamps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum InfluxPrecision {
+    /// Nanoseconds, "ns" (default)
+    #[default]
+    Nanoseconds,
+    /// Microseconds, "us"
+    Microseconds,
+    /// Milliseconds, "ms"
+    Milliseconds,
+    /// Seconds, "s"
+    Seconds,
+}
+
+impl InfluxPrecision {
+    /// Parses a precision name.
+    ///
+    /// Accepts the v2 names ("ns", "us", "ms", "s"), the v1 names ("n", "u")
+    /// and the unit names, in lowercase or uppercase.
+    ///
+    /// ## Parameters
+    ///
+    /// * `value` - A string slice containing the precision
+    ///
+    /// ## Returns
+    ///
+    /// The `InfluxPrecision`, or `None` for an unknown name.
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "ns" | "n" | "nanoseconds" => Some(Self::Nanoseconds),
+            "us" | "u" | "µs" | "microseconds" => Some(Self::Microseconds),
+            "ms" | "milliseconds" => Some(Self::Milliseconds),
+            "s" | "seconds" => Some(Self::Seconds),
+            _ => None,
+        }
+    }
+}
+
+impl From<&str> for InfluxPrecision {
+    /// Creates an `InfluxPrecision` from a string slice.
+    ///
+    /// Accepts the names of `InfluxPrecision::parse`. Any other value returns
+    /// `InfluxPrecision::Nanoseconds`.
+    ///
+    /// ## Parameters
+    ///
+    /// * `value` - A string slice containing the precision
+    ///
+    /// ## Returns
+    ///
+    /// An `InfluxPrecision` variant corresponding to the input string
+    fn from(value: &str) -> Self {
+        Self::parse(value).unwrap_or_default()
+    }
+}
+
+impl Display for InfluxPrecision {
+    /// Formats the `InfluxPrecision` as the v2 write API `precision` parameter.
+    ///
+    /// ## Returns
+    ///
+    /// A string representation of the precision (e.g., "ms").
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::Nanoseconds => write!(f, "ns"),
+            Self::Microseconds => write!(f, "us"),
+            Self::Milliseconds => write!(f, "ms"),
+            Self::Seconds => write!(f, "s"),
+        }
+    }
+}
+
+/// # InfluxVersion
+///
+/// Enum representing the InfluxDB API versions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum InfluxVersion {
+    /// InfluxDB 1.x: databases, retention policies and user credentials
+    V1,
+    /// InfluxDB 2.x: organizations, buckets and tokens (default)
+    #[default]
+    V2,
+    /// InfluxDB 3.x: databases and tokens
+    V3,
+}
+
+impl InfluxVersion {
+    /// Parses a version strictly.
+    ///
+    /// Accepts "1", "v1", "1.x" and the same forms for the other versions, in
+    /// lowercase or uppercase.
+    ///
+    /// ## Parameters
+    ///
+    /// * `value` - A string slice containing the version
+    ///
+    /// ## Returns
+    ///
+    /// The `InfluxVersion` corresponding to the input string, or `None` for unknown versions.
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim().to_lowercase();
+        let value = value.trim_start_matches('v');
+
+        match value.split('.').next() {
+            Some("1") => Some(Self::V1),
+            Some("2") => Some(Self::V2),
+            Some("3") => Some(Self::V3),
+            _ => None,
+        }
+    }
+}
+
+impl From<&str> for InfluxVersion {
+    /// Creates an `InfluxVersion` from a string slice.
+    ///
+    /// Accepts the versions of `InfluxVersion::parse`. Any other value returns
+    /// `InfluxVersion::V2`.
+    ///
+    /// ## Parameters
+    ///
+    /// * `value` - A string slice containing the version
+    ///
+    /// ## Returns
+    ///
+    /// An `InfluxVersion` variant corresponding to the input string
+    fn from(value: &str) -> Self {
+        Self::parse(value).unwrap_or_default()
+    }
+}
+
+impl Display for InfluxVersion {
+    /// Formats the `InfluxVersion`.
+    ///
+    /// ## Returns
+    ///
+    /// A string representation of the version (e.g., "v2").
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::V1 => write!(f, "v1"),
+            Self::V2 => write!(f, "v2"),
+            Self::V3 => write!(f, "v3"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_parse_version() {
+        assert_eq!(InfluxVersion::from("1.8"), InfluxVersion::V1);
+        assert_eq!(InfluxVersion::from("V3"), InfluxVersion::V3);
+        assert_eq!(InfluxVersion::from("v2"), InfluxVersion::V2);
+        assert_eq!(InfluxVersion::from("unknown"), InfluxVersion::V2);
+        assert_eq!(InfluxVersion::parse("2.7"), Some(InfluxVersion::V2));
+        assert_eq!(InfluxVersion::parse("v4"), None);
+        assert_eq!(InfluxVersion::parse("latest"), None);
+
+        let cfgs = InfluxConfigs {
+            org: "acme".into(),
+            token: "token".into(),
+            version_error: Some(ConfigsError::Invalid("version".into())),
+            ..Default::default()
+        };
+        assert!(matches!(cfgs.validate(), Err(ConfigsError::Invalid(_))));
+    }
+
+    #[test]
+    fn should_render_url() {
+        let cfgs = InfluxConfigs::default();
+        assert_eq!(cfgs.url(), "http://localhost:8086");
+        assert_eq!(cfgs.addr(), "localhost:8086");
+
+        let cfgs = InfluxConfigs::default()
+            .with_url("https://influx.example.com")
+            .unwrap();
+        assert!(cfgs.tls.enabled);
+        assert_eq!(cfgs.url(), "https://influx.example.com:443");
+
+        let cfgs = InfluxConfigs::default()
+            .with_url("http://influx.example.com/")
+            .unwrap();
+        assert_eq!(cfgs.url(), "http://influx.example.com:80");
+
+        let cfgs = InfluxConfigs::default()
+            .with_url("http://[::1]:9999")
+            .unwrap();
+        assert_eq!(cfgs.url(), "http://[::1]:9999");
+
+        assert!(InfluxConfigs::default().with_url("influx:8086").is_err());
+        assert!(
+            InfluxConfigs::default()
+                .with_url("https://proxy.example.com/influx")
+                .is_err()
+        );
+
+        let cfgs = InfluxConfigs::default().apply_url("https://proxy.example.com/influx", None);
+        assert_eq!(cfgs.url(), "http://localhost:8086");
+        assert!(matches!(cfgs.validate(), Err(ConfigsError::Invalid(_))));
+    }
+
+    #[test]
+    fn should_keep_the_port_of_host_urls_without_one() {
+        let cfgs = InfluxConfigs::default().apply_host("http://influx".into());
+        assert_eq!(cfgs.url(), "http://influx:8086");
+
+        let cfgs = InfluxConfigs::default().apply_host("https://influx:9999".into());
+        assert!(cfgs.tls.enabled);
+        assert_eq!(cfgs.url(), "https://influx:9999");
+
+        let cfgs = InfluxConfigs::default().apply_host("influx".into());
+        assert_eq!(cfgs.url(), "http://influx:8086");
+    }
+
+    #[test]
+    fn should_parse_write_options() {
+        assert_eq!(InfluxPrecision::from("MS"), InfluxPrecision::Milliseconds);
+        assert_eq!(InfluxPrecision::from("u").to_string(), "us");
+        assert_eq!(InfluxPrecision::parse("mss"), None);
+        assert_eq!(
+            parse_tags("env=prd, region = eu-west-1,,"),
+            BTreeMap::from([
+                ("env".to_owned(), "prd".to_owned()),
+                ("region".to_owned(), "eu-west-1".to_owned())
+            ])
+        );
+
+        let cfgs = InfluxConfigs {
+            org: "acme".into(),
+            token: "token".into(),
+            write: InfluxWriteConfigs {
+                default_tags: parse_tags("=orphan"),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        assert!(matches!(cfgs.validate(), Err(ConfigsError::Invalid(_))));
+
+        let cfgs = InfluxConfigs {
+            org: "acme".into(),
+            token: "token".into(),
+            write: InfluxWriteConfigs {
+                precision_error: Some(ConfigsError::Invalid("precision".into())),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        assert!(matches!(cfgs.validate(), Err(ConfigsError::Invalid(_))));
+    }
+
+    #[test]
+    fn should_require_version_fields() {
+        let v2 = InfluxConfigs {
+            org: "acme".into(),
+            bucket: "metrics".into(),
+            token: "token".into(),
+            ..Default::default()
+        };
+        assert_eq!(v2.validate(), Ok(()));
+
+        let v3 = InfluxConfigs {
+            version: InfluxVersion::V3,
+            ..v2.clone()
+        };
+        assert!(matches!(v3.validate(), Err(ConfigsError::Missing(_))));
+
+        let v1 = InfluxConfigs {
+            version: InfluxVersion::V1,
+            database: "telegraf".into(),
+            username: "reader".into(),
+            ..Default::default()
+        };
+        assert!(matches!(v1.validate(), Err(ConfigsError::Invalid(_))));
+
+        let v1 = InfluxConfigs {
+            password: "secret".into(),
+            ..v1
+        };
+        assert_eq!(v1.validate(), Ok(()));
     }
 }
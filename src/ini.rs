@@ -0,0 +1,77 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! Internal parser for the INI dialect of the AWS shared configuration files.
+//!
+//! Sections are `[name]` lines and properties are `key = value` lines. Lines
+//! starting with `#` or `;` are comments. Indented lines are nested properties
+//! of the previous key (e.g. `s3 =` followed by `  addressing_style = path`);
+//! they are not needed by this crate and are skipped.
+
+use std::collections::BTreeMap;
+
+use crate::errors::ConfigsError;
+
+/// Properties by name, for each section name.
+pub(crate) type Sections = BTreeMap<String, BTreeMap<String, String>>;
+
+/// Parses an INI document, naming it as `what` in errors.
+pub(crate) fn parse(what: &str, content: &str) -> Result<Sections, ConfigsError> {
+    let mut sections = Sections::new();
+    let mut current: Option<String> = None;
+
+    for (number, line) in content.lines().enumerate() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') || trimmed.starts_with(';') {
+            continue;
+        }
+        if line.starts_with(char::is_whitespace) && current.is_some() {
+            continue;
+        }
+
+        let invalid = || ConfigsError::Parse(format!("{} at line {}", what, number + 1));
+
+        if let Some(name) = trimmed.strip_prefix('[') {
+            let name = name.strip_suffix(']').ok_or_else(invalid)?.trim();
+            sections.entry(name.to_owned()).or_default();
+            current = Some(name.to_owned());
+            continue;
+        }
+
+        let section = current.as_ref().ok_or_else(invalid)?;
+        let (key, value) = trimmed.split_once('=').ok_or_else(invalid)?;
+        sections
+            .entry(section.clone())
+            .or_default()
+            .insert(key.trim().to_lowercase(), value.trim().to_owned());
+    }
+
+    Ok(sections)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_parse_sections() {
+        let sections = parse(
+            "config",
+            "# comment\n[default]\nregion = eu-west-1\ns3 =\n  addressing_style = path\n\n\
+             [profile dev]\nRole_Arn=arn:aws:iam::123:role/dev\n",
+        )
+        .unwrap();
+
+        assert_eq!(sections["default"]["region"], "eu-west-1");
+        assert_eq!(sections["default"]["s3"], "");
+        assert!(!sections["default"].contains_key("addressing_style"));
+        assert_eq!(
+            sections["profile dev"]["role_arn"],
+            "arn:aws:iam::123:role/dev"
+        );
+
+        assert!(parse("config", "region = eu-west-1").is_err());
+        assert!(parse("config", "[default\n").is_err());
+    }
+}
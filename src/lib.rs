@@ -51,16 +51,21 @@
 pub mod app;
 pub mod aws;
 pub mod configs;
+mod document;
 pub mod dynamic;
 pub mod dynamo;
 pub mod environment;
+pub mod errors;
 pub mod health_readiness;
 pub mod identity_server;
 pub mod influx;
+mod ini;
 pub mod kafka;
 pub mod mqtt;
 pub mod otlp;
 pub mod postgres;
 pub mod rabbitmq;
 pub mod secrets;
+mod sigv4;
 pub mod sqlite;
+mod uri;
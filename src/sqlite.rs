@@ -7,41 +7,854 @@
 //! This module provides configuration options for connecting to
 //! and working with SQLite databases.
 
+use std::{
+    collections::BTreeMap,
+    fmt::{Display, Formatter, Result as FmtResult},
+    fs::OpenOptions,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use crate::{
+    app::{APP_NAME_ENV_KEY, AppConfigs},
+    environment::Environment,
+    errors::ConfigsError,
+    uri,
+};
+
 /// # SqliteConfigs
 ///
 /// Configuration structure for SQLite database connections.
 ///
 /// This structure defines the connection parameters for SQLite databases,
-/// including database file path and (optional) credentials.
+/// including the database file path, open flags and the pragmas to apply on
+/// each connection. The defaults (WAL journal, `NORMAL` synchronous level and a
+/// busy timeout) suit concurrent readers and writers.
+///
+/// The file path may start with `~` and contain environment variables (`$VAR` or
+/// `${VAR}`, with `$$` for a literal `$`). Relative paths are resolved against
+/// `data_dir`.
 ///
 /// ## Examples
 ///
 /// ```
-/// use configs::SqliteConfigs;
+/// use configs::sqlite::SqliteConfigs;
 ///
 /// let mut sqlite_config = SqliteConfigs::default();
 /// sqlite_config.file = "/path/to/database.db".to_string();
+///
+/// assert_eq!(sqlite_config.uri(), "sqlite:///path/to/database.db?mode=rwc");
 /// ```
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone)]
 pub struct SqliteConfigs {
     /// ENV KEY: "SQLITE_FILE_NAME"
     ///
-    /// The SQLite database file path (Default: "local.db")
+    /// The SQLite database file path, or the database name in memory mode
+    /// (Default: "local.db")
     pub file: String,
+    /// ENV KEY: "APP_DATA_DIR"
+    ///
+    /// Directory relative file paths are resolved against, empty uses the working
+    /// directory (Default: "$XDG_DATA_HOME/<app name>" in `Environment::Local`, "" otherwise)
+    pub data_dir: String,
+    /// ENV KEY: "SQLITE_CREATE_DIR"
+    ///
+    /// Whether `prepare` creates the missing parent directories of the file
+    /// (Default: true in `Environment::Local`, false otherwise)
+    pub create_dir: bool,
+    /// ENV KEY: "SQLITE_IN_MEMORY"
+    ///
+    /// Whether the database is a named in-memory database shared by the
+    /// connections of the process (Default: false)
+    pub in_memory: bool,
+    /// ENV KEY: "SQLITE_READ_ONLY"
+    ///
+    /// Whether the database is opened read-only (Default: false)
+    pub read_only: bool,
+    /// ENV KEY: "SQLITE_CREATE_IF_MISSING"
+    ///
+    /// Whether the database file is created when missing (Default: true)
+    pub create_if_missing: bool,
+    /// ENV KEY: "SQLITE_JOURNAL_MODE"
+    ///
+    /// The journal mode (Default: SqliteJournalMode::Wal)
+    pub journal_mode: SqliteJournalMode,
+    /// Error raised when "SQLITE_JOURNAL_MODE" is not a known journal mode, reported
+    /// by `validate()` (Default: None)
+    pub journal_mode_error: Option<ConfigsError>,
+    /// ENV KEY: "SQLITE_SYNCHRONOUS"
+    ///
+    /// The synchronous level (Default: SqliteSynchronous::Normal)
+    pub synchronous: SqliteSynchronous,
+    /// Error raised when "SQLITE_SYNCHRONOUS" is not a known synchronous level,
+    /// reported by `validate()` (Default: None)
+    pub synchronous_error: Option<ConfigsError>,
+    /// ENV KEY: "SQLITE_BUSY_TIMEOUT"
+    ///
+    /// How long to wait for a locked database, in milliseconds (Default: 5000)
+    pub busy_timeout: Duration,
+    /// ENV KEY: "SQLITE_FOREIGN_KEYS"
+    ///
+    /// Whether foreign key constraints are enforced (Default: true)
+    pub foreign_keys: bool,
+    /// ENV KEY: "SQLITE_CACHE_SIZE"
+    ///
+    /// The page cache size, in pages when positive or in KiB when negative,
+    /// `None` uses the SQLite default (Default: None)
+    pub cache_size: Option<i64>,
+    /// ENV KEY: "SQLITE_PRAGMAS"
+    ///
+    /// Additional pragmas, as "name=value" pairs separated by commas (Default: empty)
+    pub pragmas: BTreeMap<String, String>,
 }
 
 pub const SQLITE_FILE_NAME_ENV_KEY: &str = "SQLITE_FILE_NAME";
+pub const APP_DATA_DIR_ENV_KEY: &str = "APP_DATA_DIR";
+pub const SQLITE_CREATE_DIR_ENV_KEY: &str = "SQLITE_CREATE_DIR";
+pub const SQLITE_IN_MEMORY_ENV_KEY: &str = "SQLITE_IN_MEMORY";
+pub const SQLITE_READ_ONLY_ENV_KEY: &str = "SQLITE_READ_ONLY";
+pub const SQLITE_CREATE_IF_MISSING_ENV_KEY: &str = "SQLITE_CREATE_IF_MISSING";
+pub const SQLITE_JOURNAL_MODE_ENV_KEY: &str = "SQLITE_JOURNAL_MODE";
+pub const SQLITE_SYNCHRONOUS_ENV_KEY: &str = "SQLITE_SYNCHRONOUS";
+pub const SQLITE_BUSY_TIMEOUT_ENV_KEY: &str = "SQLITE_BUSY_TIMEOUT";
+pub const SQLITE_FOREIGN_KEYS_ENV_KEY: &str = "SQLITE_FOREIGN_KEYS";
+pub const SQLITE_CACHE_SIZE_ENV_KEY: &str = "SQLITE_CACHE_SIZE";
+pub const SQLITE_PRAGMAS_ENV_KEY: &str = "SQLITE_PRAGMAS";
 
 impl SqliteConfigs {
     /// Creates a new `SqliteConfigs` instance from environment variables.
     ///
-    /// This method initializes the SQLite configuration from the environment variable
-    /// for the database file path.
+    /// This method initializes the SQLite configuration from the environment variables
+    /// for the database file path, open flags and pragmas.
+    ///
+    /// ## Returns
+    ///
+    /// A new `SqliteConfigs` from environments variables.
     pub fn new() -> Self {
-        let mut cfgs = Self::default();
+        let app_name =
+            std::env::var(APP_NAME_ENV_KEY).unwrap_or_else(|_| AppConfigs::default().name);
+        let mut cfgs = Self::for_env(&Environment::from_rust_env(), &app_name);
 
         cfgs.file = std::env::var(SQLITE_FILE_NAME_ENV_KEY).unwrap_or(cfgs.file);
+        cfgs.data_dir = std::env::var(APP_DATA_DIR_ENV_KEY).unwrap_or(cfgs.data_dir);
+        cfgs.create_dir = env_bool(SQLITE_CREATE_DIR_ENV_KEY).unwrap_or(cfgs.create_dir);
+        cfgs.in_memory = env_bool(SQLITE_IN_MEMORY_ENV_KEY).unwrap_or(cfgs.in_memory);
+        cfgs.read_only = env_bool(SQLITE_READ_ONLY_ENV_KEY).unwrap_or(cfgs.read_only);
+        cfgs.create_if_missing =
+            env_bool(SQLITE_CREATE_IF_MISSING_ENV_KEY).unwrap_or(cfgs.create_if_missing);
+        if let Ok(value) = std::env::var(SQLITE_JOURNAL_MODE_ENV_KEY) {
+            match SqliteJournalMode::parse(&value) {
+                Some(journal_mode) => cfgs.journal_mode = journal_mode,
+                None => {
+                    cfgs.journal_mode_error = Some(ConfigsError::Invalid(format!(
+                        "{} \"{}\" is not a known journal mode",
+                        SQLITE_JOURNAL_MODE_ENV_KEY, value
+                    )))
+                }
+            }
+        }
+        if let Ok(value) = std::env::var(SQLITE_SYNCHRONOUS_ENV_KEY) {
+            match SqliteSynchronous::parse(&value) {
+                Some(synchronous) => cfgs.synchronous = synchronous,
+                None => {
+                    cfgs.synchronous_error = Some(ConfigsError::Invalid(format!(
+                        "{} \"{}\" is not a known synchronous level",
+                        SQLITE_SYNCHRONOUS_ENV_KEY, value
+                    )))
+                }
+            }
+        }
+        cfgs.busy_timeout = std::env::var(SQLITE_BUSY_TIMEOUT_ENV_KEY)
+            .ok()
+            .and_then(|v| v.parse::<u64>().ok())
+            .map(Duration::from_millis)
+            .unwrap_or(cfgs.busy_timeout);
+        cfgs.foreign_keys = env_bool(SQLITE_FOREIGN_KEYS_ENV_KEY).unwrap_or(cfgs.foreign_keys);
+        cfgs.cache_size = std::env::var(SQLITE_CACHE_SIZE_ENV_KEY)
+            .ok()
+            .and_then(|v| v.parse::<i64>().ok())
+            .or(cfgs.cache_size);
+        cfgs.pragmas = std::env::var(SQLITE_PRAGMAS_ENV_KEY)
+            .map(|v| parse_pragmas(&v))
+            .unwrap_or(cfgs.pragmas);
 
         cfgs
     }
+
+    /// Creates the default `SqliteConfigs` for an environment.
+    ///
+    /// In `Environment::Local` databases are kept in the XDG data directory of the
+    /// application ("$XDG_DATA_HOME/<app name>", or "~/.local/share/<app name>"),
+    /// which `prepare` creates on a fresh checkout.
+    ///
+    /// ## Parameters
+    ///
+    /// * `env` - The environment the service runs in
+    /// * `app_name` - The application name, e.g. `AppConfigs::name`
+    ///
+    /// ## Returns
+    ///
+    /// The default `SqliteConfigs` for `env`.
+    pub fn for_env(env: &Environment, app_name: &str) -> Self {
+        let data_dir = match env {
+            Environment::Local => std::env::var("XDG_DATA_HOME")
+                .ok()
+                .filter(|v| !v.is_empty())
+                .map(PathBuf::from)
+                .or_else(|| home_dir().map(|home| home.join(".local/share")))
+                .map(|dir| dir.join(app_name).display().to_string())
+                .unwrap_or_default(),
+            _ => String::new(),
+        };
+
+        Self {
+            data_dir,
+            create_dir: env.is_local(),
+            ..Default::default()
+        }
+    }
+
+    /// Resolves the database file path.
+    ///
+    /// `~` and environment variables are expanded in `file` and `data_dir`, then
+    /// relative paths are joined to `data_dir`.
+    ///
+    /// ## Returns
+    ///
+    /// The resolved path, or a `ConfigsError::Missing` naming an undefined variable.
+    pub fn path(&self) -> Result<PathBuf, ConfigsError> {
+        let lookup = |name: &str| std::env::var(name).ok();
+        let file = PathBuf::from(expand(&self.file, lookup)?);
+        if self.in_memory || file.is_absolute() || self.data_dir.is_empty() {
+            return Ok(file);
+        }
+
+        Ok(PathBuf::from(expand(&self.data_dir, lookup)?).join(file))
+    }
+
+    /// Checks that the database file can be opened, creating its directory if allowed.
+    ///
+    /// The parent directory must exist, unless `create_dir` is set, and be writable
+    /// unless the database is read-only, even when the file exists, since SQLite
+    /// creates its journal files next to it. A database on a read-only filesystem
+    /// is reported as such, suggesting "SQLITE_READ_ONLY". In-memory databases are
+    /// always accepted.
+    ///
+    /// Checking writability creates and removes a probe file next to the database.
+    ///
+    /// ## Returns
+    ///
+    /// The resolved path, or a `ConfigsError` describing the problem.
+    pub fn prepare(&self) -> Result<PathBuf, ConfigsError> {
+        let path = self.path()?;
+        if self.in_memory {
+            return Ok(path);
+        }
+
+        let parent = match path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+            _ => PathBuf::from("."),
+        };
+        if !parent.is_dir() {
+            if !self.create_dir {
+                return Err(ConfigsError::FileNotFound(parent.display().to_string()));
+            }
+            std::fs::create_dir_all(&parent).map_err(|e| fs_error(&parent, e.kind()))?;
+        }
+
+        if self.read_only {
+            if !path.is_file() {
+                return Err(ConfigsError::FileNotFound(path.display().to_string()));
+            }
+            return Ok(path);
+        }
+
+        if path.exists() {
+            OpenOptions::new()
+                .append(true)
+                .open(&path)
+                .map_err(|e| fs_error(&path, e.kind()))?;
+        } else if !self.create_if_missing {
+            return Err(ConfigsError::FileNotFound(path.display().to_string()));
+        }
+
+        // SQLite also creates its journal, or the WAL `-wal` and `-shm` files, in the directory
+        let probe = parent.join(format!(
+            ".{}.probe",
+            path.file_name().unwrap_or_default().to_string_lossy()
+        ));
+        OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&probe)
+            .map_err(|e| fs_error(&parent, e.kind()))?;
+        let _ = std::fs::remove_file(&probe);
+
+        Ok(path)
+    }
+
+    /// Renders the database as a `sqlite://` URI.
+    ///
+    /// The path is resolved with `path`, falling back to `file` when it cannot be.
+    /// The `mode` query option is `ro`, `rw` or `rwc` (read-write-create), and
+    /// in-memory databases use `mode=memory&cache=shared` so every connection of
+    /// the process opens the same database.
+    ///
+    /// ## Returns
+    ///
+    /// The URI, e.g. "sqlite://data/app.db?mode=rwc".
+    pub fn uri(&self) -> String {
+        let path = self
+            .path()
+            .map(|path| path.display().to_string())
+            .unwrap_or_else(|_| self.file.clone());
+        let path = path
+            .split('/')
+            .map(uri::encode)
+            .collect::<Vec<_>>()
+            .join("/");
+
+        let mode = match (self.in_memory, self.read_only, self.create_if_missing) {
+            (true, _, _) => "memory&cache=shared",
+            (false, true, _) => "ro",
+            (false, false, true) => "rwc",
+            (false, false, false) => "rw",
+        };
+
+        format!("sqlite://{}?mode={}", path, mode)
+    }
+
+    /// Returns the pragmas to execute on each new connection, in order.
+    ///
+    /// The journal mode is skipped for in-memory databases, which only support
+    /// the `MEMORY` and `OFF` modes. Entries of `pragmas` come last and override
+    /// the dedicated settings.
+    ///
+    /// ## Returns
+    ///
+    /// The pragma names and values.
+    pub fn connection_pragmas(&self) -> Vec<(String, String)> {
+        let mut pragmas = Vec::new();
+
+        if !self.in_memory {
+            pragmas.push(("journal_mode".to_owned(), self.journal_mode.to_string()));
+        }
+        pragmas.push(("synchronous".to_owned(), self.synchronous.to_string()));
+        pragmas.push((
+            "busy_timeout".to_owned(),
+            self.busy_timeout.as_millis().to_string(),
+        ));
+        pragmas.push((
+            "foreign_keys".to_owned(),
+            if self.foreign_keys { "ON" } else { "OFF" }.to_owned(),
+        ));
+        if let Some(cache_size) = self.cache_size {
+            pragmas.push(("cache_size".to_owned(), cache_size.to_string()));
+        }
+
+        for (name, value) in &self.pragmas {
+            pragmas.retain(|(n, _)| n != name);
+            pragmas.push((name.clone(), value.clone()));
+        }
+
+        pragmas
+    }
+
+    /// Returns the `PRAGMA` statements of `connection_pragmas`.
+    ///
+    /// ## Returns
+    ///
+    /// The statements, e.g. "PRAGMA journal_mode = WAL;".
+    pub fn pragma_statements(&self) -> Vec<String> {
+        self.connection_pragmas()
+            .iter()
+            .map(|(name, value)| format!("PRAGMA {} = {};", name, value))
+            .collect()
+    }
+
+    /// Validates the open flags and pragmas.
+    ///
+    /// The journal mode and synchronous level must be known, a read-only database
+    /// cannot be created, a named database is required in memory mode, the
+    /// variables of the path must be defined, the busy timeout must fit SQLite's
+    /// 32-bit milliseconds, and pragma names must be identifiers with values free
+    /// of statement separators. The filesystem is checked by `prepare`.
+    ///
+    /// ## Returns
+    ///
+    /// `Ok(())` if the configuration is consistent, a `ConfigsError` otherwise.
+    pub fn validate(&self) -> Result<(), ConfigsError> {
+        if let Some(e) = self
+            .journal_mode_error
+            .as_ref()
+            .or(self.synchronous_error.as_ref())
+        {
+            return Err(e.clone());
+        }
+        if self.file.is_empty() {
+            return Err(ConfigsError::Missing(SQLITE_FILE_NAME_ENV_KEY.into()));
+        }
+        self.path()?;
+        if self.read_only && self.create_if_missing && !self.in_memory {
+            return Err(ConfigsError::Invalid(format!(
+                "{} cannot be combined with {}",
+                SQLITE_READ_ONLY_ENV_KEY, SQLITE_CREATE_IF_MISSING_ENV_KEY
+            )));
+        }
+        if self.busy_timeout.as_millis() > i32::MAX as u128 {
+            return Err(ConfigsError::Invalid(format!(
+                "{} is too large",
+                SQLITE_BUSY_TIMEOUT_ENV_KEY
+            )));
+        }
+
+        for (name, value) in &self.pragmas {
+            let valid_name = !name.is_empty()
+                && name
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.');
+            if !valid_name || value.is_empty() || value.contains(';') {
+                return Err(ConfigsError::Invalid(format!(
+                    "invalid SQLite pragma \"{}={}\"",
+                    name, value
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn home_dir() -> Option<PathBuf> {
+    std::env::var("HOME")
+        .or_else(|_| std::env::var("USERPROFILE"))
+        .ok()
+        .filter(|v| !v.is_empty())
+        .map(PathBuf::from)
+}
+
+/// Expands a leading `~` and the `$VAR` and `${VAR}` variables, read with `lookup`.
+///
+/// `$$` stands for a literal `$`. Inserted values are not expanded again.
+fn expand(value: &str, lookup: impl Fn(&str) -> Option<String>) -> Result<String, ConfigsError> {
+    let missing = |name: &str| ConfigsError::Missing(format!("{} in \"{}\"", name, value));
+
+    let (mut expanded, mut cursor) = match value.strip_prefix('~') {
+        Some(rest) if rest.is_empty() || rest.starts_with('/') => {
+            let home = lookup("HOME")
+                .or_else(|| lookup("USERPROFILE"))
+                .filter(|v| !v.is_empty())
+                .ok_or_else(|| missing("HOME"))?;
+            (format!("{}{}", home, rest), home.len())
+        }
+        _ => (value.to_owned(), 0),
+    };
+
+    while let Some(offset) = expanded[cursor..].find('$') {
+        let start = cursor + offset;
+        let rest = &expanded[start + 1..];
+        if rest.starts_with('$') {
+            expanded.replace_range(start..start + 2, "$");
+            cursor = start + 1;
+            continue;
+        }
+
+        let (name, len) = match rest.strip_prefix('{') {
+            Some(braced) => {
+                let end = braced.find('}').ok_or_else(|| {
+                    ConfigsError::Invalid(format!("unclosed ${{ in \"{}\"", value))
+                })?;
+                (&braced[..end], end + 2)
+            }
+            None => {
+                let end = rest
+                    .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                    .unwrap_or(rest.len());
+                (&rest[..end], end)
+            }
+        };
+        if name.is_empty() {
+            return Err(ConfigsError::Invalid(format!(
+                "invalid variable in \"{}\"",
+                value
+            )));
+        }
+
+        let var = lookup(name).ok_or_else(|| missing(name))?;
+        expanded.replace_range(start..start + 1 + len, &var);
+        cursor = start + var.len();
+    }
+
+    Ok(expanded)
+}
+
+/// Describes a filesystem error on `path`.
+fn fs_error(path: &Path, kind: ErrorKind) -> ConfigsError {
+    let path = path.display();
+
+    match kind {
+        ErrorKind::ReadOnlyFilesystem => ConfigsError::Invalid(format!(
+            "{} is on a read-only filesystem, set {}=true to open the database read-only",
+            path, SQLITE_READ_ONLY_ENV_KEY
+        )),
+        ErrorKind::PermissionDenied => ConfigsError::Invalid(format!("{} is not writable", path)),
+        ErrorKind::NotFound => ConfigsError::FileNotFound(path.to_string()),
+        kind => ConfigsError::Invalid(format!("{} cannot be opened: {}", path, kind)),
+    }
+}
+
+fn env_bool(key: &str) -> Option<bool> {
+    std::env::var(key).ok().and_then(|v| v.parse::<bool>().ok())
+}
+
+/// Parses "name=value" pairs separated by commas, ignoring empty entries.
+fn parse_pragmas(value: &str) -> BTreeMap<String, String> {
+    value
+        .split(',')
+        .filter(|pair| !pair.trim().is_empty())
+        .map(|pair| {
+            let (name, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (name.trim().to_lowercase(), value.trim().to_owned())
+        })
+        .collect()
+}
+
+impl Default for SqliteConfigs {
+    fn default() -> Self {
+        Self {
+            file: "local.db".to_owned(),
+            data_dir: Default::default(),
+            create_dir: false,
+            in_memory: false,
+            read_only: false,
+            create_if_missing: true,
+            journal_mode: Default::default(),
+            journal_mode_error: None,
+            synchronous: Default::default(),
+            synchronous_error: None,
+            busy_timeout: Duration::from_millis(5000),
+            foreign_keys: true,
+            cache_size: None,
+            pragmas: Default::default(),
+        }
+    }
+}
+
+/// # SqliteJournalMode
+///
+/// Enum representing SQLite journal modes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SqliteJournalMode {
+    /// Rollback journal deleted after each transaction
+    Delete,
+    /// Rollback journal truncated after each transaction
+    Truncate,
+    /// Rollback journal header zeroed after each transaction
+    Persist,
+    /// Rollback journal kept in memory
+    Memory,
+    /// Write-ahead log, allowing readers during writes (default)
+    #[default]
+    Wal,
+    /// No rollback journal
+    Off,
+}
+
+impl SqliteJournalMode {
+    /// Parses a journal mode strictly.
+    ///
+    /// The parsing is case-insensitive.
+    ///
+    /// ## Parameters
+    ///
+    /// * `value` - A string slice containing the journal mode
+    ///
+    /// ## Returns
+    ///
+    /// The `SqliteJournalMode` corresponding to the input string, or `None` for unknown modes.
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_uppercase().as_str() {
+            "DELETE" => Some(Self::Delete),
+            "TRUNCATE" => Some(Self::Truncate),
+            "PERSIST" => Some(Self::Persist),
+            "MEMORY" => Some(Self::Memory),
+            "WAL" => Some(Self::Wal),
+            "OFF" => Some(Self::Off),
+            _ => None,
+        }
+    }
+}
+
+impl From<&str> for SqliteJournalMode {
+    /// Creates a `SqliteJournalMode` from a string slice.
+    ///
+    /// Accepts the modes of `SqliteJournalMode::parse`. Unknown values return
+    /// `SqliteJournalMode::Wal`.
+    ///
+    /// ## Parameters
+    ///
+    /// * `value` - A string slice containing the journal mode
+    ///
+    /// ## Returns
+    ///
+    /// A `SqliteJournalMode` variant corresponding to the input string
+    fn from(value: &str) -> Self {
+        Self::parse(value).unwrap_or_default()
+    }
+}
+
+impl Display for SqliteJournalMode {
+    /// Formats the `SqliteJournalMode` as its pragma value.
+    ///
+    /// ## Returns
+    ///
+    /// A string representation of the journal mode (e.g., "WAL").
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::Delete => write!(f, "DELETE"),
+            Self::Truncate => write!(f, "TRUNCATE"),
+            Self::Persist => write!(f, "PERSIST"),
+            Self::Memory => write!(f, "MEMORY"),
+            Self::Wal => write!(f, "WAL"),
+            Self::Off => write!(f, "OFF"),
+        }
+    }
+}
+
+/// # SqliteSynchronous
+///
+/// Enum representing SQLite synchronous levels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SqliteSynchronous {
+    /// No syncing, fastest and unsafe on power loss
+    Off,
+    /// Syncs at critical moments, durable with WAL except on power loss (default)
+    #[default]
+    Normal,
+    /// Syncs on every commit
+    Full,
+    /// Like `Full`, also syncing the directory of deleted journals
+    Extra,
+}
+
+impl SqliteSynchronous {
+    /// Parses a synchronous level strictly.
+    ///
+    /// The parsing is case-insensitive and accepts the numeric levels (0-3).
+    ///
+    /// ## Parameters
+    ///
+    /// * `value` - A string slice containing the synchronous level
+    ///
+    /// ## Returns
+    ///
+    /// The `SqliteSynchronous` corresponding to the input string, or `None` for unknown levels.
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_uppercase().as_str() {
+            "OFF" | "0" => Some(Self::Off),
+            "NORMAL" | "1" => Some(Self::Normal),
+            "FULL" | "2" => Some(Self::Full),
+            "EXTRA" | "3" => Some(Self::Extra),
+            _ => None,
+        }
+    }
+}
+
+impl From<&str> for SqliteSynchronous {
+    /// Creates a `SqliteSynchronous` from a string slice.
+    ///
+    /// Accepts the levels of `SqliteSynchronous::parse`. Unknown values return
+    /// `SqliteSynchronous::Normal`.
+    ///
+    /// ## Parameters
+    ///
+    /// * `value` - A string slice containing the synchronous level
+    ///
+    /// ## Returns
+    ///
+    /// A `SqliteSynchronous` variant corresponding to the input string
+    fn from(value: &str) -> Self {
+        Self::parse(value).unwrap_or_default()
+    }
+}
+
+impl Display for SqliteSynchronous {
+    /// Formats the `SqliteSynchronous` as its pragma value.
+    ///
+    /// ## Returns
+    ///
+    /// A string representation of the level (e.g., "NORMAL").
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::Off => write!(f, "OFF"),
+            Self::Normal => write!(f, "NORMAL"),
+            Self::Full => write!(f, "FULL"),
+            Self::Extra => write!(f, "EXTRA"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_render_uri() {
+        let cfgs = SqliteConfigs {
+            file: "data/my app.db".into(),
+            ..Default::default()
+        };
+        assert_eq!(cfgs.uri(), "sqlite://data/my%20app.db?mode=rwc");
+
+        let cfgs = SqliteConfigs {
+            read_only: true,
+            create_if_missing: false,
+            ..cfgs
+        };
+        assert_eq!(cfgs.uri(), "sqlite://data/my%20app.db?mode=ro");
+
+        let cfgs = SqliteConfigs {
+            file: "cache".into(),
+            in_memory: true,
+            ..Default::default()
+        };
+        assert_eq!(cfgs.uri(), "sqlite://cache?mode=memory&cache=shared");
+    }
+
+    #[test]
+    fn should_list_connection_pragmas() {
+        let cfgs = SqliteConfigs {
+            cache_size: Some(-2000),
+            pragmas: parse_pragmas("temp_store=MEMORY, synchronous=FULL"),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            cfgs.pragma_statements(),
+            vec![
+                "PRAGMA journal_mode = WAL;",
+                "PRAGMA busy_timeout = 5000;",
+                "PRAGMA foreign_keys = ON;",
+                "PRAGMA cache_size = -2000;",
+                "PRAGMA synchronous = FULL;",
+                "PRAGMA temp_store = MEMORY;",
+            ]
+        );
+        assert_eq!(cfgs.validate(), Ok(()));
+    }
+
+    #[test]
+    fn should_resolve_path() {
+        let home = home_dir().unwrap();
+
+        let cfgs = SqliteConfigs {
+            file: "~/app.db".into(),
+            ..Default::default()
+        };
+        assert_eq!(cfgs.path(), Ok(home.join("app.db")));
+
+        let cfgs = SqliteConfigs {
+            file: "db/${HOME}.db".into(),
+            data_dir: "/var/lib/app".into(),
+            ..Default::default()
+        };
+        assert_eq!(
+            cfgs.path(),
+            Ok(PathBuf::from(format!(
+                "/var/lib/app/db/{}.db",
+                home.display()
+            )))
+        );
+
+        let cfgs = SqliteConfigs {
+            file: "$SQLITE_TEST_UNDEFINED_VARIABLE/app.db".into(),
+            ..Default::default()
+        };
+        assert!(matches!(cfgs.path(), Err(ConfigsError::Missing(_))));
+        assert!(matches!(cfgs.validate(), Err(ConfigsError::Missing(_))));
+
+        let lookup = |name: &str| (name == "LOOP").then(|| "$LOOP$".to_owned());
+        assert_eq!(
+            expand("/data/$LOOP/${LOOP}/$$HOME.db", lookup),
+            Ok("/data/$LOOP$/$LOOP$/$HOME.db".to_owned())
+        );
+        assert!(matches!(
+            expand("~/app.db", lookup),
+            Err(ConfigsError::Missing(_))
+        ));
+
+        let local = SqliteConfigs::for_env(&Environment::Local, "orders");
+        assert!(local.create_dir);
+        assert!(local.data_dir.ends_with("orders"));
+        assert!(!SqliteConfigs::for_env(&Environment::Prod, "orders").create_dir);
+    }
+
+    #[test]
+    fn should_prepare_filesystem() {
+        let dir = std::env::temp_dir().join(format!("configs-sqlite-{}", std::process::id()));
+        let cfgs = SqliteConfigs {
+            file: "nested/app.db".into(),
+            data_dir: dir.display().to_string(),
+            ..Default::default()
+        };
+        assert!(matches!(cfgs.prepare(), Err(ConfigsError::FileNotFound(_))));
+
+        let cfgs = SqliteConfigs {
+            create_dir: true,
+            ..cfgs
+        };
+        assert_eq!(cfgs.prepare(), Ok(dir.join("nested/app.db")));
+        assert!(dir.join("nested").is_dir());
+        assert!(!dir.join("nested/.app.db.probe").exists());
+
+        let read_only = SqliteConfigs {
+            read_only: true,
+            create_if_missing: false,
+            ..cfgs.clone()
+        };
+        assert!(matches!(
+            read_only.prepare(),
+            Err(ConfigsError::FileNotFound(_))
+        ));
+
+        std::fs::write(dir.join("nested/app.db"), b"").unwrap();
+        assert_eq!(cfgs.prepare(), Ok(dir.join("nested/app.db")));
+        assert!(!dir.join("nested/.app.db.probe").exists());
+        assert_eq!(read_only.prepare(), Ok(dir.join("nested/app.db")));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn should_validate_flags_and_pragmas() {
+        let cfgs = SqliteConfigs {
+            read_only: true,
+            ..Default::default()
+        };
+        assert!(matches!(cfgs.validate(), Err(ConfigsError::Invalid(_))));
+
+        let cfgs = SqliteConfigs {
+            pragmas: parse_pragmas("user_version=1; DROP TABLE users"),
+            ..Default::default()
+        };
+        assert!(matches!(cfgs.validate(), Err(ConfigsError::Invalid(_))));
+
+        assert_eq!(
+            SqliteJournalMode::parse("wal"),
+            Some(SqliteJournalMode::Wal)
+        );
+        assert_eq!(SqliteJournalMode::parse("WAL2"), None);
+        assert_eq!(
+            SqliteSynchronous::parse("1"),
+            Some(SqliteSynchronous::Normal)
+        );
+        assert_eq!(SqliteSynchronous::parse("FUL"), None);
+
+        let cfgs = SqliteConfigs {
+            synchronous_error: Some(ConfigsError::Invalid("synchronous".into())),
+            ..Default::default()
+        };
+        assert!(matches!(cfgs.validate(), Err(ConfigsError::Invalid(_))));
+    }
 }